@@ -0,0 +1,279 @@
+//! Attestations binding a public key to metadata about how it was generated,
+//! signed by a manufacturer key.
+//!
+//! These are intended for supply-chain attestation of device identity keys:
+//! a manufacturer creates a [`ProvenanceAttestation`] for each device key at
+//! provisioning time, which relying parties check against the manufacturer's
+//! [`VerifyingKey`].
+//!
+//! # Encoding
+//!
+//! The signed portion of an attestation is the concatenation of:
+//!
+//! - the domain separator `ecdsa-key-provenance-v1`
+//! - the subject key, SEC1-encoded with point compression and prefixed by its
+//!   length as a big endian `u16`
+//! - the generation time as a big endian `u64`
+//! - the device ID, prefixed by its length as a big endian `u16`
+//! - the 32-byte policy hash
+//!
+//! A serialized attestation is the signed portion followed by the fixed-size
+//! signature over it.
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    Error, Result, Signature, SignatureSize, SigningKey, VerifyingKey,
+};
+use alloc::vec::Vec;
+use core::{
+    convert::{TryFrom, TryInto},
+    ops::Add,
+};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    ops::Invert,
+    sec1::{FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+    weierstrass::Curve,
+    zeroize::Zeroize,
+    AffinePoint, ProjectiveArithmetic, Scalar,
+};
+use signature::{Signer, Verifier};
+
+/// Domain separator which begins every attestation
+const DOMAIN: &[u8] = b"ecdsa-key-provenance-v1";
+
+/// Size of a policy hash
+pub const POLICY_HASH_SIZE: usize = 32;
+
+/// Metadata about the generation of a key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Provenance {
+    /// Time at which the key was generated, in seconds since the Unix epoch
+    pub generated_at: u64,
+
+    /// Identifier of the device which generated the key (at most 65535 bytes)
+    pub device_id: Vec<u8>,
+
+    /// Hash of the policy under which the key was generated and is managed
+    pub policy_hash: [u8; POLICY_HASH_SIZE],
+}
+
+/// Public key and its [`Provenance`], signed by a manufacturer key.
+#[derive(Clone, Debug)]
+pub struct ProvenanceAttestation<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    subject_key: VerifyingKey<C>,
+    provenance: Provenance,
+    signature: Signature<C>,
+}
+
+impl<C> ProvenanceAttestation<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Attest to the provenance of `subject_key` using `manufacturer_key`.
+    ///
+    /// Returns an error if the device ID is longer than 65535 bytes.
+    pub fn create(
+        manufacturer_key: &SigningKey<C>,
+        subject_key: VerifyingKey<C>,
+        provenance: Provenance,
+    ) -> Result<Self>
+    where
+        SigningKey<C>: Signer<Signature<C>>,
+        Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    {
+        let signature = manufacturer_key.try_sign(&signed_bytes(&subject_key, &provenance)?)?;
+
+        Ok(Self {
+            subject_key,
+            provenance,
+            signature,
+        })
+    }
+
+    /// Verify this attestation was signed by `manufacturer_key`.
+    pub fn verify(&self, manufacturer_key: &VerifyingKey<C>) -> Result<()>
+    where
+        VerifyingKey<C>: Verifier<Signature<C>>,
+    {
+        manufacturer_key.verify(
+            &signed_bytes(&self.subject_key, &self.provenance)?,
+            &self.signature,
+        )
+    }
+
+    /// Parse a serialized attestation.
+    ///
+    /// The signature is not verified: use [`ProvenanceAttestation::verify`]
+    /// before trusting the contents.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut bytes = bytes.strip_prefix(DOMAIN).ok_or_else(Error::new)?;
+
+        let subject_key = VerifyingKey::from_sec1_bytes(take_u16_prefixed(&mut bytes)?)?;
+        let generated_at =
+            u64::from_be_bytes(take(&mut bytes, 8)?.try_into().map_err(|_| Error::new())?);
+        let device_id = take_u16_prefixed(&mut bytes)?.to_vec();
+        let policy_hash = take(&mut bytes, POLICY_HASH_SIZE)?
+            .try_into()
+            .map_err(|_| Error::new())?;
+        let signature = Signature::try_from(bytes)?;
+
+        Ok(Self {
+            subject_key,
+            provenance: Provenance {
+                generated_at,
+                device_id,
+                policy_hash,
+            },
+            signature,
+        })
+    }
+
+    /// Serialize this attestation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // The device ID length was checked when the attestation was created
+        // or parsed
+        let mut bytes =
+            signed_bytes(&self.subject_key, &self.provenance).expect("device ID too long");
+
+        bytes.extend_from_slice(self.signature.as_ref());
+        bytes
+    }
+
+    /// Get the key whose provenance is attested to.
+    pub fn subject_key(&self) -> &VerifyingKey<C> {
+        &self.subject_key
+    }
+
+    /// Get the attested provenance of the subject key.
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    /// Get the manufacturer signature.
+    pub fn signature(&self) -> &Signature<C> {
+        &self.signature
+    }
+}
+
+/// Serialize the portion of an attestation which is signed.
+fn signed_bytes<C>(subject_key: &VerifyingKey<C>, provenance: &Provenance) -> Result<Vec<u8>>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    let subject_key = subject_key.to_encoded_point(true);
+    let mut bytes = Vec::from(DOMAIN);
+
+    put_u16_prefixed(&mut bytes, subject_key.as_bytes())?;
+    bytes.extend_from_slice(&provenance.generated_at.to_be_bytes());
+    put_u16_prefixed(&mut bytes, &provenance.device_id)?;
+    bytes.extend_from_slice(&provenance.policy_hash);
+    Ok(bytes)
+}
+
+/// Append `data` prefixed by its length as a big endian `u16`.
+fn put_u16_prefixed(bytes: &mut Vec<u8>, data: &[u8]) -> Result<()> {
+    let len = u16::try_from(data.len()).map_err(|_| Error::new())?;
+    bytes.extend_from_slice(&len.to_be_bytes());
+    bytes.extend_from_slice(data);
+    Ok(())
+}
+
+/// Split `len` bytes off the front of `bytes`.
+fn take<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if bytes.len() < len {
+        return Err(Error::new());
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+/// Split data prefixed by its length as a big endian `u16` off the front of
+/// `bytes`.
+fn take_u16_prefixed<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let len = take(bytes, 2)?;
+    take(bytes, usize::from(u16::from_be_bytes([len[0], len[1]])))
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{Provenance, ProvenanceAttestation, DOMAIN};
+    use crate::{Signature, VerifyingKey};
+    use core::convert::TryFrom;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    /// Public key from RFC 6979 Appendix 2.5 (NIST P-256), compressed
+    const SUBJECT_KEY: [u8; 33] =
+        hex!("0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6");
+
+    /// Signature from RFC 6979 Appendix 2.5 (NIST P-256 + SHA-256, "sample")
+    const SIGNATURE: [u8; 64] = hex!(
+        "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+        "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+    );
+
+    fn example() -> ProvenanceAttestation<MockCurve> {
+        ProvenanceAttestation {
+            subject_key: VerifyingKey::from_sec1_bytes(&SUBJECT_KEY).unwrap(),
+            provenance: Provenance {
+                generated_at: 1_600_000_000,
+                device_id: b"device-1".to_vec(),
+                policy_hash: [0x42; 32],
+            },
+            signature: Signature::try_from(&SIGNATURE[..]).unwrap(),
+        }
+    }
+
+    #[test]
+    fn encoding_roundtrip() {
+        let bytes = example().to_bytes();
+        let expected = [
+            DOMAIN,
+            &[0, 33],
+            &SUBJECT_KEY,
+            &hex!("000000005f5e1000"),
+            &[0, 8],
+            b"device-1",
+            &[0x42; 32],
+            &SIGNATURE,
+        ]
+        .concat();
+        assert_eq!(bytes, expected);
+
+        let parsed = ProvenanceAttestation::<MockCurve>::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            parsed.subject_key().to_encoded_point(true).as_bytes(),
+            &SUBJECT_KEY[..]
+        );
+        assert_eq!(parsed.provenance(), example().provenance());
+        assert_eq!(parsed.signature(), example().signature());
+    }
+
+    #[test]
+    fn reject_malformed_attestations() {
+        let bytes = example().to_bytes();
+
+        for len in &[0, DOMAIN.len(), DOMAIN.len() + 2, bytes.len() - 1] {
+            assert!(ProvenanceAttestation::<MockCurve>::from_bytes(&bytes[..*len]).is_err());
+        }
+
+        assert!(
+            ProvenanceAttestation::<MockCurve>::from_bytes(&[&bytes[..], &[0]].concat()).is_err()
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Queue which coalesces signing requests into batches.
+//!
+//! Requests submitted to a [`BatchSigner`] are signed together by
+//! [`SigningKey::try_sign_many`] the next time the queue is flushed, which
+//! inverts the ephemeral scalars of the whole batch at once. The queue has a
+//! fixed capacity: once it is full, new requests are rejected until it is
+//! flushed, so callers can shed or delay load rather than queueing without
+//! bound.
+//!
+//! The queue can also flush itself: with [`BatchSigner::with_batch_size`],
+//! the submission which brings the number of pending requests up to the batch
+//! size signs the batch before returning.
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    Error, Result, Signature, SignatureSize, SigningKey,
+};
+use alloc::vec::Vec;
+use core::fmt;
+use elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, weierstrass::Curve, zeroize::Zeroize,
+    ProjectiveArithmetic, Scalar,
+};
+use signature::digest::{BlockInput, FixedOutput, Reset, Update};
+use std::sync::{
+    mpsc::{self, Receiver, Sender, TryRecvError},
+    Arc, Mutex, MutexGuard,
+};
+
+/// Pending request: message digest and where to send its signature
+type Request<C, D> = (D, Sender<Result<Signature<C>>>);
+
+/// Signing queue which signs message digests of type `D` in batches.
+///
+/// Any thread may submit requests, and any thread (e.g. a dedicated worker,
+/// or the submitter of the request which filled the queue) may flush them.
+pub struct BatchSigner<C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Key used to sign all requests
+    signing_key: SigningKey<C>,
+
+    /// Requests which have not yet been signed
+    pending: Mutex<Vec<Request<C, D>>>,
+
+    /// Maximum number of pending requests
+    capacity: usize,
+
+    /// Number of pending requests at which the queue flushes itself
+    batch_size: Option<usize>,
+}
+
+impl<C, D> BatchSigner<C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Create a queue which holds at most `capacity` pending requests.
+    pub fn new(signing_key: SigningKey<C>, capacity: usize) -> Self {
+        Self {
+            signing_key,
+            pending: Mutex::new(Vec::with_capacity(capacity)),
+            capacity,
+            batch_size: None,
+        }
+    }
+
+    /// Flush the queue from [`BatchSigner::submit`] whenever `batch_size`
+    /// requests are pending.
+    ///
+    /// A `batch_size` larger than the capacity is never reached, so the
+    /// queue only flushes itself when it is full.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(core::cmp::max(batch_size, 1));
+        self
+    }
+
+    /// Queue the given message digest for signing, returning a handle to
+    /// its eventual signature.
+    ///
+    /// If this fills a batch (see [`BatchSigner::with_batch_size`]), the
+    /// batch is signed before returning.
+    ///
+    /// Returns an error if the queue is full.
+    pub fn submit(&self, digest: D) -> Result<SignatureHandle<C>> {
+        let mut pending = self.lock();
+
+        if pending.len() >= self.capacity {
+            return Err(Error::new());
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        pending.push((digest, sender));
+
+        if matches!(self.batch_size, Some(batch_size) if pending.len() >= batch_size) {
+            let batch = core::mem::take(&mut *pending);
+            drop(pending);
+            self.sign_batch(batch);
+        }
+
+        Ok(SignatureHandle { receiver })
+    }
+
+    /// Sign all pending requests as a single batch, returning the number of
+    /// requests signed.
+    ///
+    /// If signing the batch fails, every request in it receives an error
+    /// whose source is a [`BatchError`] wrapping the original error.
+    pub fn flush(&self) -> usize {
+        let batch = core::mem::take(&mut *self.lock());
+        self.sign_batch(batch)
+    }
+
+    /// Sign the given requests as a single batch, returning their number.
+    fn sign_batch(&self, batch: Vec<Request<C, D>>) -> usize {
+        let count = batch.len();
+
+        if count == 0 {
+            return 0;
+        }
+
+        let (digests, senders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+
+        match self.signing_key.try_sign_many(digests) {
+            Ok(signatures) => {
+                for (sender, signature) in senders.iter().zip(signatures) {
+                    // The handle may have been dropped
+                    let _ = sender.send(Ok(signature));
+                }
+            }
+            Err(err) => {
+                let err = BatchError(Arc::new(err));
+
+                for sender in &senders {
+                    let _ = sender.send(Err(Error::from_source(err.clone())));
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Get the number of pending requests.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Are there no pending requests?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the maximum number of pending requests.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Lock the pending requests, ignoring poisoning (the queue is left
+    /// consistent by every operation which holds the lock).
+    fn lock(&self) -> MutexGuard<'_, Vec<Request<C, D>>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Failure to sign a batch, shared by every request in it.
+///
+/// This is the [`std::error::Error::source`] of the error each request in
+/// the batch receives, and its own source is the original error.
+#[derive(Clone, Debug)]
+pub struct BatchError(Arc<Error>);
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to sign batch")
+    }
+}
+
+impl std::error::Error for BatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+/// Handle to the signature for a request submitted to a [`BatchSigner`].
+pub struct SignatureHandle<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    receiver: Receiver<Result<Signature<C>>>,
+}
+
+impl<C> SignatureHandle<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Block until the request has been signed.
+    ///
+    /// Returns an error if signing failed, or if the [`BatchSigner`] was
+    /// dropped before the request was signed.
+    pub fn wait(self) -> Result<Signature<C>> {
+        self.receiver.recv().map_err(|_| Error::new())?
+    }
+
+    /// Get the signature if the request has been signed, without blocking.
+    pub fn try_get(&self) -> Option<Result<Signature<C>>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(Error::new())),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{BatchError, BatchSigner, SignatureHandle};
+    use crate::{dev::test_curve::SigningKey, Error, Signature};
+    use core::convert::TryFrom;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+    use sha2::{Digest, Sha256};
+    use signature::Signer;
+    use std::{
+        error::Error as _,
+        sync::{mpsc, Arc},
+        vec::Vec,
+    };
+
+    /// Signature from RFC 6979 Appendix 2.5 (NIST P-256 + SHA-256, "sample")
+    const EXAMPLE_SIGNATURE: [u8; 64] = hex!(
+        "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+        "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+    );
+
+    #[test]
+    fn handle_receives_signature() {
+        let (sender, receiver) = mpsc::channel();
+        let handle = SignatureHandle::<MockCurve> { receiver };
+        assert!(handle.try_get().is_none());
+
+        let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+        sender.send(Ok(signature)).unwrap();
+        assert_eq!(handle.wait().unwrap(), signature);
+    }
+
+    #[test]
+    fn handle_errors_when_queue_dropped() {
+        let (sender, receiver) = mpsc::channel::<Result<Signature<MockCurve>, Error>>();
+        let handle = SignatureHandle { receiver };
+        drop(sender);
+
+        assert!(handle.try_get().unwrap().is_err());
+        assert!(handle.wait().is_err());
+    }
+
+    #[test]
+    fn flush_matches_individual_signatures() {
+        let key_bytes = hex!("0123456789abcdef");
+        let signing_key = SigningKey::from_bytes(&key_bytes).unwrap();
+        let signer = BatchSigner::new(SigningKey::from_bytes(&key_bytes).unwrap(), 3);
+        let msgs: [&[u8]; 3] = [b"first message", b"second message", b"third message"];
+
+        let handles = msgs
+            .iter()
+            .map(|msg| signer.submit(Sha256::new().chain(msg)).unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(signer.submit(Sha256::new()).is_err());
+        assert_eq!(signer.flush(), 3);
+        assert!(signer.is_empty());
+
+        for (msg, handle) in msgs.iter().zip(handles) {
+            assert_eq!(handle.wait().unwrap(), signing_key.sign(msg));
+        }
+    }
+
+    #[test]
+    fn submit_flushes_full_batch() {
+        let key_bytes = hex!("0123456789abcdef");
+        let signing_key = SigningKey::from_bytes(&key_bytes).unwrap();
+        let signer =
+            BatchSigner::new(SigningKey::from_bytes(&key_bytes).unwrap(), 3).with_batch_size(2);
+
+        let first = signer
+            .submit(Sha256::new().chain(b"first message"))
+            .unwrap();
+        assert!(first.try_get().is_none());
+        assert_eq!(signer.len(), 1);
+
+        let second = signer
+            .submit(Sha256::new().chain(b"second message"))
+            .unwrap();
+        assert!(signer.is_empty());
+        assert_eq!(
+            first.try_get().unwrap().unwrap(),
+            signing_key.sign(b"first message")
+        );
+        assert_eq!(second.wait().unwrap(), signing_key.sign(b"second message"));
+    }
+
+    #[test]
+    fn batch_error_forwards_source() {
+        let source = Error::from_source(crate::RngError);
+        let err = Error::from_source(BatchError(Arc::new(source)));
+
+        let batch_err = err.source().unwrap();
+        assert!(batch_err.is::<BatchError>());
+
+        let source = batch_err.source().unwrap();
+        assert!(source.source().unwrap().is::<crate::RngError>());
+    }
+}
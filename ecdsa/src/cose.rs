@@ -0,0 +1,445 @@
+//! Support for signatures in the formats used by COSE ([RFC 8152]) and
+//! [WebAuthn].
+//!
+//! COSE encodes ECDSA signatures (i.e. `ES256`, `ES384`, and `ES512`) as the
+//! big endian `r` and `s` scalars concatenated with no added framing, which is
+//! the same fixed-size encoding used by [`Signature`].
+//!
+//! WebAuthn authenticators, on the other hand, emit ASN.1 DER-encoded
+//! signatures in assertions and attestation statements, even though the
+//! algorithm is identified using a COSE algorithm identifier.
+//!
+//! With the `verify` feature enabled, [`VerifyingKey`]s can also be encoded as
+//! `COSE_Key` structures ([RFC 8152 Section 13.1.1]) using the canonical CBOR
+//! encoding required by CTAP2, and decoded from the EC2 `COSE_Key`s found in
+//! WebAuthn attested credential data.
+//!
+//! [RFC 8152]: https://tools.ietf.org/html/rfc8152#section-8.1
+//! [RFC 8152 Section 13.1.1]: https://tools.ietf.org/html/rfc8152#section-13.1.1
+//! [WebAuthn]: https://www.w3.org/TR/webauthn-2/#sctn-signature-attestation-types
+
+use crate::{der, Result, Signature, SignatureBytes, SignatureSize};
+use core::{convert::TryFrom, ops::Add};
+use elliptic_curve::{generic_array::ArrayLength, weierstrass::Curve, FieldSize};
+
+#[cfg(feature = "verify")]
+use {
+    crate::{Error, VerifyingKey},
+    elliptic_curve::{
+        bigint::Encoding as _,
+        consts::U1,
+        sec1::{
+            EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize,
+            UntaggedPointSize,
+        },
+        AffinePoint, FieldBytes, ProjectiveArithmetic,
+    },
+};
+
+/// `COSE_Key` common parameter: key type (`kty`)
+#[cfg(feature = "verify")]
+const COSE_KEY_KTY: i64 = 1;
+
+/// `COSE_Key` common parameter: algorithm (`alg`)
+#[cfg(feature = "verify")]
+const COSE_KEY_ALG: i64 = 3;
+
+/// `COSE_Key` EC2 parameter: curve (`crv`)
+#[cfg(feature = "verify")]
+const COSE_KEY_CRV: i64 = -1;
+
+/// `COSE_Key` EC2 parameter: x-coordinate
+#[cfg(feature = "verify")]
+const COSE_KEY_X: i64 = -2;
+
+/// `COSE_Key` EC2 parameter: y-coordinate
+#[cfg(feature = "verify")]
+const COSE_KEY_Y: i64 = -3;
+
+/// `COSE_Key` key type for elliptic curve keys with x and y coordinates
+#[cfg(feature = "verify")]
+const COSE_KTY_EC2: i64 = 2;
+
+/// COSE identifiers for an elliptic curve and the ECDSA algorithm used with
+/// it, as registered in the [IANA COSE registries].
+///
+/// [IANA COSE registries]: https://www.iana.org/assignments/cose/cose.xhtml
+pub trait CoseParameters: Curve {
+    /// COSE elliptic curve identifier (e.g. `1` for P-256)
+    const COSE_CRV: i64;
+
+    /// COSE algorithm identifier (e.g. `-7` for `ES256`)
+    const COSE_ALG: i64;
+}
+
+impl<C> Signature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Parse a signature from the fixed-size encoding used by COSE.
+    pub fn from_cose(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+
+    /// Serialize this signature using the fixed-size encoding used by COSE.
+    pub fn to_cose(&self) -> SignatureBytes<C> {
+        self.bytes.clone()
+    }
+
+    /// Parse an ASN.1 DER-encoded signature as emitted by WebAuthn
+    /// authenticators.
+    pub fn from_webauthn(bytes: &[u8]) -> Result<Self>
+    where
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+    {
+        Self::from_der(bytes)
+    }
+
+    /// Serialize this signature as ASN.1 DER, as expected by WebAuthn
+    /// relying parties.
+    pub fn to_webauthn(&self) -> der::Signature<C>
+    where
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+    {
+        self.to_der()
+    }
+}
+
+#[cfg(feature = "verify")]
+impl<C> VerifyingKey<C>
+where
+    C: Curve + CoseParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`VerifyingKey`] from a CBOR-encoded EC2 `COSE_Key`.
+    ///
+    /// The `kty`, `crv`, `x`, and `y` parameters are required, and `crv` (and
+    /// `alg`, if present) must match `C`. Other parameters are ignored if
+    /// their values are integers or byte strings. Compressed points (i.e. a
+    /// boolean `y` parameter) are not supported.
+    pub fn decode_cose_key(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = CborDecoder { bytes };
+        let (kty, alg, crv, x, y) = decoder.cose_key()?;
+
+        if !decoder.bytes.is_empty()
+            || kty != Some(CborValue::Int(COSE_KTY_EC2))
+            || crv != Some(CborValue::Int(C::COSE_CRV))
+            || (alg.is_some() && alg != Some(CborValue::Int(C::COSE_ALG)))
+        {
+            return Err(Error::new());
+        }
+
+        match (x, y) {
+            (Some(CborValue::Bytes(x)), Some(CborValue::Bytes(y)))
+                if x.len() == C::UInt::BYTE_SIZE && y.len() == C::UInt::BYTE_SIZE =>
+            {
+                Self::from_encoded_point(&EncodedPoint::from_affine_coordinates(
+                    FieldBytes::<C>::from_slice(x),
+                    FieldBytes::<C>::from_slice(y),
+                    false,
+                ))
+            }
+            _ => Err(Error::new()),
+        }
+    }
+
+    /// Serialize this [`VerifyingKey`] as a CBOR-encoded `COSE_Key`, writing
+    /// it into the provided buffer.
+    ///
+    /// Map entries are sorted as required by the CTAP2 canonical CBOR
+    /// encoding. Returns the written portion of the buffer.
+    pub fn encode_cose_key<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        let point = self.to_encoded_point(false);
+        let (x, y) = point.as_bytes()[1..].split_at(C::UInt::BYTE_SIZE);
+
+        let mut encoder = CborEncoder { buf, pos: 0 };
+        encoder.map_header(5)?;
+        encoder.int(COSE_KEY_KTY)?;
+        encoder.int(COSE_KTY_EC2)?;
+        encoder.int(COSE_KEY_ALG)?;
+        encoder.int(C::COSE_ALG)?;
+        encoder.int(COSE_KEY_CRV)?;
+        encoder.int(C::COSE_CRV)?;
+        encoder.int(COSE_KEY_X)?;
+        encoder.bytes(x)?;
+        encoder.int(COSE_KEY_Y)?;
+        encoder.bytes(y)?;
+        Ok(encoder.finish())
+    }
+}
+
+/// Minimal encoder for the subset of CBOR used by `COSE_Key`.
+#[cfg(feature = "verify")]
+struct CborEncoder<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+#[cfg(feature = "verify")]
+impl<'a> CborEncoder<'a> {
+    /// Encode a map header for the given number of entries.
+    fn map_header(&mut self, len: u64) -> Result<()> {
+        self.header(5, len)
+    }
+
+    /// Encode an integer.
+    fn int(&mut self, n: i64) -> Result<()> {
+        if n < 0 {
+            self.header(1, !n as u64)
+        } else {
+            self.header(0, n as u64)
+        }
+    }
+
+    /// Encode a byte string.
+    fn bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        self.header(2, bytes.len() as u64)?;
+        self.write(bytes)
+    }
+
+    /// Encode an initial byte for the given major type and argument,
+    /// followed by the argument if it doesn't fit in the initial byte.
+    fn header(&mut self, major_type: u8, arg: u64) -> Result<()> {
+        let major_type = major_type << 5;
+
+        if arg < 24 {
+            self.write(&[major_type | arg as u8])
+        } else if arg <= u64::from(u8::MAX) {
+            self.write(&[major_type | 24, arg as u8])
+        } else if arg <= u64::from(u16::MAX) {
+            self.write(&[major_type | 25])?;
+            self.write(&(arg as u16).to_be_bytes())
+        } else if arg <= u64::from(u32::MAX) {
+            self.write(&[major_type | 26])?;
+            self.write(&(arg as u32).to_be_bytes())
+        } else {
+            self.write(&[major_type | 27])?;
+            self.write(&arg.to_be_bytes())
+        }
+    }
+
+    /// Write raw bytes into the output buffer.
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        let end = self.pos.checked_add(bytes.len()).ok_or_else(Error::new)?;
+        self.buf
+            .get_mut(self.pos..end)
+            .ok_or_else(Error::new)?
+            .copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+
+    /// Get the encoded output.
+    fn finish(self) -> &'a [u8] {
+        &self.buf[..self.pos]
+    }
+}
+
+/// `COSE_Key` parameters needed to decode a key: `kty`, `alg`, `crv`, `x`,
+/// and `y`.
+#[cfg(feature = "verify")]
+type CoseKeyParams<'a> = (
+    Option<CborValue<'a>>,
+    Option<CborValue<'a>>,
+    Option<CborValue<'a>>,
+    Option<CborValue<'a>>,
+    Option<CborValue<'a>>,
+);
+
+/// CBOR data item which may appear as a `COSE_Key` label or value.
+#[cfg(feature = "verify")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum CborValue<'a> {
+    /// Unsigned or negative integer
+    Int(i64),
+
+    /// Byte string
+    Bytes(&'a [u8]),
+}
+
+/// Minimal decoder for the subset of CBOR used by `COSE_Key`.
+#[cfg(feature = "verify")]
+struct CborDecoder<'a> {
+    bytes: &'a [u8],
+}
+
+#[cfg(feature = "verify")]
+impl<'a> CborDecoder<'a> {
+    /// Decode a map of `COSE_Key` parameters, rejecting duplicate labels.
+    fn cose_key(&mut self) -> Result<CoseKeyParams<'a>> {
+        let (mut kty, mut alg, mut crv, mut x, mut y) = (None, None, None, None, None);
+
+        if self.header()? != 5 {
+            return Err(Error::new());
+        }
+
+        for _ in 0..self.arg()? {
+            let label = match self.value()? {
+                CborValue::Int(label) => label,
+                CborValue::Bytes(_) => return Err(Error::new()),
+            };
+
+            let value = self.value()?;
+            let param = match label {
+                COSE_KEY_KTY => &mut kty,
+                COSE_KEY_ALG => &mut alg,
+                COSE_KEY_CRV => &mut crv,
+                COSE_KEY_X => &mut x,
+                COSE_KEY_Y => &mut y,
+                _ => continue,
+            };
+
+            if param.replace(value).is_some() {
+                return Err(Error::new());
+            }
+        }
+
+        Ok((kty, alg, crv, x, y))
+    }
+
+    /// Decode an integer or byte string.
+    fn value(&mut self) -> Result<CborValue<'a>> {
+        let major_type = self.header()?;
+        let arg = self.arg()?;
+
+        match major_type {
+            0 => i64::try_from(arg).map(CborValue::Int),
+            1 => i64::try_from(arg).map(|n| CborValue::Int(-1 - n)),
+            2 => {
+                let len = usize::try_from(arg).map_err(|_| Error::new())?;
+                return self.take(len).map(CborValue::Bytes);
+            }
+            _ => return Err(Error::new()),
+        }
+        .map_err(|_| Error::new())
+    }
+
+    /// Decode the major type of the next data item, leaving its additional
+    /// information to be decoded by [`CborDecoder::arg`].
+    fn header(&self) -> Result<u8> {
+        self.bytes
+            .first()
+            .map(|byte| byte >> 5)
+            .ok_or_else(Error::new)
+    }
+
+    /// Decode the argument of the data item whose major type was decoded by
+    /// [`CborDecoder::header`]. Indefinite lengths are not supported.
+    fn arg(&mut self) -> Result<u64> {
+        let info = self.take(1)?[0] & 0x1f;
+
+        let len = match info {
+            0..=23 => return Ok(u64::from(info)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return Err(Error::new()),
+        };
+
+        Ok(self
+            .take(len)?
+            .iter()
+            .fold(0, |arg, &byte| arg << 8 | u64::from(byte)))
+    }
+
+    /// Split `len` bytes off the front of the input.
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            return Err(Error::new());
+        }
+
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    type Signature = crate::Signature<MockCurve>;
+
+    /// ES256 signature over "sample" from RFC 6979 Appendix 2.5
+    const COSE_SIGNATURE: [u8; 64] = hex!(
+        "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+        "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+    );
+
+    /// Public key from RFC 6979 Appendix 2.5
+    #[cfg(feature = "verify")]
+    const UNCOMPRESSED_KEY: [u8; 65] = hex!(
+        "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+        "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+    );
+
+    #[test]
+    #[cfg(all(feature = "dev", feature = "verify"))]
+    fn cose_key_encoding() {
+        let key = crate::VerifyingKey::<MockCurve>::from_sec1_bytes(&UNCOMPRESSED_KEY).unwrap();
+        let mut buf = [0u8; 77];
+        let cose_key = key.encode_cose_key(&mut buf).unwrap();
+
+        // {1: 2, 3: -7, -1: 1, -2: h'...', -3: h'...'}
+        assert_eq!(&cose_key[..10], &hex!("a5010203262001215820"));
+        assert_eq!(&cose_key[10..42], &UNCOMPRESSED_KEY[1..33]);
+        assert_eq!(&cose_key[42..45], &hex!("225820"));
+        assert_eq!(&cose_key[45..], &UNCOMPRESSED_KEY[33..]);
+
+        assert!(key.encode_cose_key(&mut [0u8; 76]).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "dev", feature = "verify"))]
+    fn cose_key_decoding() {
+        type VerifyingKey = crate::VerifyingKey<MockCurve>;
+
+        let key = VerifyingKey::from_sec1_bytes(&UNCOMPRESSED_KEY).unwrap();
+        let mut buf = [0u8; 77];
+        let cose_key = key.encode_cose_key(&mut buf).unwrap().to_vec();
+
+        let decoded = VerifyingKey::decode_cose_key(&cose_key).unwrap();
+        assert_eq!(
+            decoded.to_encoded_point(false).as_bytes(),
+            &UNCOMPRESSED_KEY[..]
+        );
+
+        // Without `alg`, and with a `kid` which is ignored
+        let mut without_alg = hex!("a50102024131").to_vec();
+        without_alg.extend_from_slice(&cose_key[5..]);
+        assert!(VerifyingKey::decode_cose_key(&without_alg).is_ok());
+
+        for (index, value) in &[(2, 0x01), (4, 0x27), (6, 0x02), (9, 0x40)] {
+            let mut modified = cose_key.clone();
+            modified[*index] = *value;
+            assert!(VerifyingKey::decode_cose_key(&modified).is_err());
+        }
+
+        let mut missing_y = cose_key[..42].to_vec();
+        missing_y[0] = 0xa4;
+        assert!(VerifyingKey::decode_cose_key(&missing_y).is_err());
+
+        let mut duplicate_kty = cose_key.clone();
+        duplicate_kty[0] = 0xa6;
+        duplicate_kty.extend_from_slice(&[0x01, 0x02]);
+        assert!(VerifyingKey::decode_cose_key(&duplicate_kty).is_err());
+
+        assert!(VerifyingKey::decode_cose_key(&[&cose_key[..], &[0]].concat()).is_err());
+        assert!(VerifyingKey::decode_cose_key(&cose_key[..76]).is_err());
+    }
+
+    #[test]
+    fn webauthn_to_cose() {
+        let webauthn_signature = Signature::from_cose(&COSE_SIGNATURE).unwrap().to_webauthn();
+
+        let signature = Signature::from_webauthn(webauthn_signature.as_bytes()).unwrap();
+        assert_eq!(signature.to_cose().as_slice(), &COSE_SIGNATURE[..]);
+    }
+}
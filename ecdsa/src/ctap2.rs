@@ -0,0 +1,84 @@
+//! Authenticator-side helpers for CTAP2 (FIDO2) signatures.
+//!
+//! Authenticators sign the concatenation of the authenticator data and the
+//! hash of the client data (`authData || clientDataHash`) when generating
+//! both assertions and self/packed attestation statements, and emit the
+//! signature using ASN.1 DER.
+//!
+//! The credential public key returned when making a credential can be encoded
+//! using [`VerifyingKey::encode_cose_key`].
+//!
+//! See the [CTAP2 specification] for more information.
+//!
+//! [CTAP2 specification]: https://fidoalliance.org/specs/fido-v2.1-ps-20210615/fido-client-to-authenticator-protocol-v2.1-ps-20210615.html
+
+use crate::{
+    der,
+    hazmat::{DigestPrimitive, FromDigest, SignPrimitive},
+    Result, Signature, SignatureSize, SigningKey,
+};
+use core::ops::Add;
+use elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, weierstrass::Curve, zeroize::Zeroize, FieldSize,
+    ProjectiveArithmetic, Scalar,
+};
+use signature::{digest::Digest, DigestSigner};
+
+#[cfg(doc)]
+use crate::VerifyingKey;
+
+/// Size of the SHA-256 hash of the serialized client data (`clientDataHash`)
+pub const CLIENT_DATA_HASH_SIZE: usize = 32;
+
+/// Compute the digest of the CTAP2 signature base, i.e.
+/// `authData || clientDataHash`.
+pub fn signature_base<D: Digest>(
+    authenticator_data: &[u8],
+    client_data_hash: &[u8; CLIENT_DATA_HASH_SIZE],
+) -> D {
+    D::new().chain(authenticator_data).chain(client_data_hash)
+}
+
+impl<C> SigningKey<C>
+where
+    Self: DigestSigner<C::Digest, Signature<C>>,
+    C: Curve + DigestPrimitive + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+    der::MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+{
+    /// Sign the given authenticator data and client data hash, returning an
+    /// ASN.1 DER-encoded signature suitable for inclusion in a CTAP2
+    /// assertion or attestation statement.
+    pub fn try_sign_authenticator_data(
+        &self,
+        authenticator_data: &[u8],
+        client_data_hash: &[u8; CLIENT_DATA_HASH_SIZE],
+    ) -> Result<der::Signature<C>> {
+        let digest = signature_base::<C::Digest>(authenticator_data, client_data_hash);
+        self.try_sign_digest(digest)
+            .map(|signature| signature.to_der())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::signature_base;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn signature_base_concatenates_inputs() {
+        let authenticator_data = [0xaa; 37];
+        let client_data_hash = [0xbb; 32];
+
+        let mut message = [0u8; 69];
+        message[..37].copy_from_slice(&authenticator_data);
+        message[37..].copy_from_slice(&client_data_hash);
+
+        assert_eq!(
+            signature_base::<Sha256>(&authenticator_data, &client_data_hash).finalize(),
+            Sha256::digest(&message)
+        );
+    }
+}
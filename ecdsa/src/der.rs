@@ -1,9 +1,10 @@
 //! Support for ECDSA signatures encoded as ASN.1 DER.
 
-use crate::{Error, Result};
+use crate::{Error, Result, SignatureSize};
 use core::{
     convert::{TryFrom, TryInto},
     fmt,
+    marker::PhantomData,
     ops::{Add, Range},
 };
 use der::{asn1::UIntBytes, Decodable};
@@ -90,6 +91,7 @@ where
 
     /// Serialize this signature as a boxed byte slice
     #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     pub fn to_bytes(&self) -> Box<[u8]> {
         self.as_bytes().to_vec().into_boxed_slice()
     }
@@ -148,7 +150,139 @@ where
 {
     type Error = Error;
 
+    #[deny(clippy::indexing_slicing, clippy::panic)]
     fn try_from(input: &[u8]) -> Result<Self> {
+        let SignatureRef {
+            r_range, s_range, ..
+        } = SignatureRef::<C>::try_from(input)?;
+
+        let mut bytes = SignatureBytes::<C>::default();
+        bytes
+            .get_mut(..input.len())
+            .ok_or_else(Error::new)?
+            .copy_from_slice(input);
+
+        Ok(Signature {
+            bytes,
+            r_range,
+            s_range,
+        })
+    }
+}
+
+impl<C> TryFrom<Signature<C>> for super::Signature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(sig: Signature<C>) -> Result<super::Signature<C>> {
+        SignatureRef::from(&sig).try_into()
+    }
+}
+
+impl<C> From<super::Signature<C>> for Signature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    fn from(sig: super::Signature<C>) -> Signature<C> {
+        sig.to_der()
+    }
+}
+
+impl<C> From<&super::Signature<C>> for Signature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    fn from(sig: &super::Signature<C>) -> Signature<C> {
+        sig.to_der()
+    }
+}
+
+impl<C> TryFrom<Signature<C>> for crate::FixedSignature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(sig: Signature<C>) -> Result<crate::FixedSignature<C>> {
+        super::Signature::try_from(sig).map(Into::into)
+    }
+}
+
+impl<C> From<crate::FixedSignature<C>> for Signature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    fn from(sig: crate::FixedSignature<C>) -> Signature<C> {
+        super::Signature::from(sig).to_der()
+    }
+}
+
+/// Borrowed ASN.1 DER-encoded signature.
+///
+/// Validates the encoding of a signature and references it within the input
+/// buffer rather than copying it, which is useful when parsing a large number
+/// of signatures.
+#[derive(Clone, Debug)]
+pub struct SignatureRef<'a, C: Curve> {
+    /// ASN.1 DER-encoded signature data
+    bytes: &'a [u8],
+
+    /// Range of the `r` value within the signature
+    r_range: Range<usize>,
+
+    /// Range of the `s` value within the signature
+    s_range: Range<usize>,
+
+    /// Elliptic curve the signature is for
+    curve: PhantomData<C>,
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl<'a, C: Curve> SignatureRef<'a, C> {
+    /// Get the length of the signature in bytes
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Borrow this signature as a byte slice
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Get the `r` component of the signature (leading zeros removed)
+    pub(crate) fn r(&self) -> &'a [u8] {
+        self.bytes.get(self.r_range.clone()).unwrap_or_default()
+    }
+
+    /// Get the `s` component of the signature (leading zeros removed)
+    pub(crate) fn s(&self) -> &'a [u8] {
+        self.bytes.get(self.s_range.clone()).unwrap_or_default()
+    }
+}
+
+impl<C: Curve> AsRef<[u8]> for SignatureRef<'_, C> {
+    fn as_ref(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'a, C: Curve> TryFrom<&'a [u8]> for SignatureRef<'a, C> {
+    type Error = Error;
+
+    #[deny(clippy::indexing_slicing, clippy::panic)]
+    fn try_from(input: &'a [u8]) -> Result<Self> {
         let (r, s) = der::Decoder::new(input)
             .sequence(|decoder| Ok((UIntBytes::decode(decoder)?, UIntBytes::decode(decoder)?)))
             .map_err(|_| Error::new())?;
@@ -164,35 +298,75 @@ where
             return Err(Error::new());
         }
 
-        let mut bytes = SignatureBytes::<C>::default();
-        bytes[..s_range.end].copy_from_slice(input);
-
-        Ok(Signature {
-            bytes,
+        Ok(SignatureRef {
+            bytes: input,
             r_range,
             s_range,
+            curve: PhantomData,
         })
     }
 }
 
-impl<C> TryFrom<Signature<C>> for super::Signature<C>
+impl<'a, C> From<&'a Signature<C>> for SignatureRef<'a, C>
 where
     C: Curve,
     MaxSize<C>: ArrayLength<u8>,
     <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    fn from(sig: &'a Signature<C>) -> SignatureRef<'a, C> {
+        SignatureRef {
+            bytes: sig.as_bytes(),
+            r_range: sig.r_range.clone(),
+            s_range: sig.s_range.clone(),
+            curve: PhantomData,
+        }
+    }
+}
+
+impl<C> From<SignatureRef<'_, C>> for Signature<C>
+where
+    C: Curve,
+    MaxSize<C>: ArrayLength<u8>,
+    <FieldSize<C> as Add>::Output: Add<MaxOverhead> + ArrayLength<u8>,
+{
+    fn from(sig: SignatureRef<'_, C>) -> Signature<C> {
+        let mut bytes = SignatureBytes::<C>::default();
+        bytes[..sig.len()].copy_from_slice(sig.bytes);
+
+        Signature {
+            bytes,
+            r_range: sig.r_range,
+            s_range: sig.s_range,
+        }
+    }
+}
+
+impl<C> TryFrom<SignatureRef<'_, C>> for super::Signature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
 {
     type Error = Error;
 
-    fn try_from(sig: Signature<C>) -> Result<super::Signature<C>> {
+    #[deny(clippy::indexing_slicing, clippy::panic)]
+    fn try_from(sig: SignatureRef<'_, C>) -> Result<super::Signature<C>> {
         let mut bytes = super::SignatureBytes::<C>::default();
-        let r_begin = C::UInt::BYTE_SIZE.saturating_sub(sig.r().len());
-        let s_begin = bytes.len().saturating_sub(sig.s().len());
-        bytes[r_begin..C::UInt::BYTE_SIZE].copy_from_slice(sig.r());
-        bytes[s_begin..].copy_from_slice(sig.s());
+        let (r_bytes, s_bytes) = bytes.split_at_mut(C::UInt::BYTE_SIZE);
+        copy_right_aligned(r_bytes, sig.r())?;
+        copy_right_aligned(s_bytes, sig.s())?;
         Self::try_from(bytes.as_slice())
     }
 }
 
+/// Copy `src` into the rightmost bytes of `dst`, left padding it with zeros
+fn copy_right_aligned(dst: &mut [u8], src: &[u8]) -> Result<()> {
+    let offset = dst.len().checked_sub(src.len()).ok_or_else(Error::new)?;
+    dst.get_mut(offset..)
+        .ok_or_else(Error::new)?
+        .copy_from_slice(src);
+    Ok(())
+}
+
 /// Locate the range within a slice at which a particular subslice is located
 fn find_scalar_range(outer: &[u8], inner: &[u8]) -> Result<Range<usize>> {
     let outer_start = outer.as_ptr() as usize;
@@ -216,6 +390,7 @@ where
 
 #[cfg(all(test, feature = "arithmetic"))]
 mod tests {
+    use core::convert::TryFrom;
     use elliptic_curve::dev::MockCurve;
     use signature::Signature as _;
 
@@ -240,6 +415,27 @@ mod tests {
         assert_eq!(signature1, signature2);
     }
 
+    #[test]
+    fn test_fixed_to_asn1_signature_conversions() {
+        let fixed = crate::FixedSignature::<MockCurve>::from_bytes(&EXAMPLE_SIGNATURE).unwrap();
+        let signature = Signature::from(fixed);
+        assert_eq!(fixed.as_ref(), signature.as_ref());
+
+        let der = crate::DerSignature::<MockCurve>::from(fixed);
+        assert_eq!(der.as_bytes(), signature.to_der().as_bytes());
+        assert_eq!(crate::FixedSignature::try_from(der).unwrap(), fixed);
+    }
+
+    #[test]
+    fn test_borrowed_asn1_signature() {
+        let fixed = Signature::from_bytes(&EXAMPLE_SIGNATURE).unwrap();
+        let der = fixed.to_der();
+        let der_ref = crate::DerSignatureRef::<MockCurve>::try_from(der.as_bytes()).unwrap();
+        assert_eq!(der_ref.as_bytes().as_ptr(), der.as_bytes().as_ptr());
+        assert_eq!(Signature::try_from(der_ref).unwrap(), fixed);
+        assert!(crate::DerSignatureRef::<MockCurve>::try_from(&der.as_bytes()[1..]).is_err());
+    }
+
     #[test]
     fn test_asn1_too_short_signature() {
         assert!(Signature::from_der(&[]).is_err());
@@ -1,23 +1,30 @@
 //! Development-related functionality.
 
-use crate::hazmat::FromDigest;
+use crate::hazmat::{bits2field, FromDigest};
+use core::sync::atomic::{AtomicUsize, Ordering};
 use elliptic_curve::{
     bigint::Encoding as _,
-    consts::U32,
     dev::{MockCurve, Scalar, ScalarBytes},
     subtle::{ConditionallySelectable, ConstantTimeLess},
     Curve,
 };
-use signature::digest::Digest;
+use signature::{digest::Digest, Error, Signer};
+
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+// Not part of the public API: only exported for use by this crate's tests
+#[doc(hidden)]
+pub mod test_curve;
 
 type UInt = <MockCurve as Curve>::UInt;
 
 impl FromDigest<MockCurve> for Scalar {
     fn from_digest<D>(digest: D) -> Self
     where
-        D: Digest<OutputSize = U32>,
+        D: Digest,
     {
-        let uint = UInt::from_be_bytes(digest.finalize().into());
+        let uint = UInt::from_be_bytes(bits2field::<MockCurve>(&digest.finalize()).into());
         let overflow = !uint.ct_lt(&MockCurve::ORDER);
         let scalar = uint.wrapping_add(&UInt::conditional_select(
             &UInt::ZERO,
@@ -30,6 +37,132 @@ impl FromDigest<MockCurve> for Scalar {
     }
 }
 
+#[cfg(feature = "cose")]
+impl crate::cose::CoseParameters for MockCurve {
+    const COSE_CRV: i64 = 1;
+    const COSE_ALG: i64 = -7;
+}
+
+#[cfg(feature = "jose")]
+impl crate::jwt::JwsParameters for MockCurve {
+    const JWS_ALG: &'static str = "ES256";
+}
+
+#[cfg(feature = "jwk")]
+impl crate::jwk::JwkParameters for MockCurve {
+    const CRV: &'static str = "P-256";
+}
+
+#[cfg(feature = "multibase")]
+impl crate::multibase::MulticodecParameters for MockCurve {
+    const PUBLIC_KEY_CODE: u64 = 0x1200;
+}
+
+#[cfg(feature = "ssh")]
+impl crate::ssh::SshParameters for MockCurve {
+    const CURVE_IDENTIFIER: &'static str = "nistp256";
+}
+
+/// Test double for a signer backed by a remote service or hardware device
+/// (e.g. a KMS or HSM), which wraps a local signer and can be configured to
+/// simulate latency, transient failures, and malformed signatures.
+///
+/// Only the synchronous [`Signer`] trait is implemented, with latency
+/// simulated by blocking the calling thread. The signer is [`Sync`] (if `S`
+/// is), so it can be shared between threads, e.g. to exercise a connection
+/// pool.
+pub struct MockRemoteSigner<S> {
+    /// Signer which computes the signatures
+    signer: S,
+
+    /// Number of upcoming signing operations which will fail
+    remaining_failures: AtomicUsize,
+
+    /// Whether to corrupt the signatures returned by the signer
+    corrupt_signatures: bool,
+
+    /// Simulated round-trip time to the signing service
+    #[cfg(feature = "std")]
+    latency: Duration,
+}
+
+impl<S> MockRemoteSigner<S> {
+    /// Wrap the given signer. By default, signing succeeds immediately.
+    pub fn new(signer: S) -> Self {
+        Self {
+            signer,
+            remaining_failures: AtomicUsize::new(0),
+            corrupt_signatures: false,
+            #[cfg(feature = "std")]
+            latency: Duration::default(),
+        }
+    }
+
+    /// Fail the next `count` signing operations with an error, as though the
+    /// service were unavailable.
+    pub fn fail_next(self, count: usize) -> Self {
+        self.remaining_failures.store(count, Ordering::SeqCst);
+        self
+    }
+
+    /// Flip a bit in every returned signature, so that it fails verification
+    /// (or, rarely, fails to parse, in which case an error is returned).
+    pub fn corrupt_signatures(mut self, corrupt: bool) -> Self {
+        self.corrupt_signatures = corrupt;
+        self
+    }
+
+    /// Block for `latency` before each signing operation.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+}
+
+impl<S, T> Signer<T> for MockRemoteSigner<S>
+where
+    S: Signer<T>,
+    T: signature::Signature,
+{
+    fn try_sign(&self, msg: &[u8]) -> Result<T, Error> {
+        #[cfg(feature = "std")]
+        std::thread::sleep(self.latency);
+
+        let failed = self
+            .remaining_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |count| {
+                count.checked_sub(1)
+            })
+            .is_ok();
+
+        if failed {
+            return Err(Error::new());
+        }
+
+        let signature = self.signer.try_sign(msg)?;
+
+        if !self.corrupt_signatures {
+            return Ok(signature);
+        }
+
+        let mut bytes = [0u8; MAX_SIGNATURE_SIZE];
+        let len = signature.as_ref().len();
+        let corrupted = bytes.get_mut(..len).ok_or_else(Error::new)?;
+        corrupted.copy_from_slice(signature.as_ref());
+
+        if let Some(last) = corrupted.last_mut() {
+            *last ^= 1;
+        }
+
+        T::from_bytes(corrupted)
+    }
+}
+
+/// Maximum size of a signature which [`MockRemoteSigner`] can corrupt
+const MAX_SIGNATURE_SIZE: usize = 144;
+
 // TODO(tarcieri): implement full set of tests from ECDSA2VS
 // <https://csrc.nist.gov/CSRC/media/Projects/Cryptographic-Algorithm-Validation-Program/documents/dss2/ecdsa2vs.pdf>
 
@@ -57,6 +190,146 @@ pub struct TestVector {
     pub s: &'static [u8],
 }
 
+/// Public key encoding which must be rejected when parsing a verifying key
+pub struct InvalidPointVector {
+    /// Why the encoding is invalid
+    pub description: &'static str,
+
+    /// SEC1-encoded point
+    pub sec1: &'static [u8],
+}
+
+/// Invalid public key encodings which must be rejected for every curve.
+///
+/// These are checked by [`new_invalid_point_test!`] in addition to any
+/// curve-specific vectors.
+///
+/// [`new_invalid_point_test!`]: crate::new_invalid_point_test
+pub const GENERIC_INVALID_POINTS: &[InvalidPointVector] = &[
+    InvalidPointVector {
+        description: "empty input",
+        sec1: &[],
+    },
+    InvalidPointVector {
+        description: "identity point",
+        sec1: &[0x00],
+    },
+    InvalidPointVector {
+        description: "identity point with trailing data",
+        sec1: &[0x00, 0x00],
+    },
+    InvalidPointVector {
+        description: "compressed tag without x-coordinate",
+        sec1: &[0x02],
+    },
+    InvalidPointVector {
+        description: "compressed tag without x-coordinate",
+        sec1: &[0x03],
+    },
+    InvalidPointVector {
+        description: "uncompressed tag without coordinates",
+        sec1: &[0x04],
+    },
+    InvalidPointVector {
+        description: "hybrid encoding",
+        sec1: &[0x06, 0x01],
+    },
+];
+
+/// Invalid NIST P-256 public key encodings (x ≥ p, x not on the curve, and
+/// points which don't satisfy the curve equation), for use with
+/// [`new_invalid_point_test!`] by the `p256` crate.
+///
+/// [`new_invalid_point_test!`]: crate::new_invalid_point_test
+pub const P256_INVALID_POINTS: &[InvalidPointVector] = &[
+    InvalidPointVector {
+        description: "compressed x = p",
+        sec1: &[
+            0x02, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff,
+        ],
+    },
+    InvalidPointVector {
+        description: "compressed x = 2^256 - 1",
+        sec1: &[
+            0x03, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff,
+        ],
+    },
+    InvalidPointVector {
+        description: "compressed x = 1, which has no point on the curve (only the twist)",
+        sec1: &[
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x01,
+        ],
+    },
+    InvalidPointVector {
+        description: "uncompressed (0, 0)",
+        sec1: &[
+            0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ],
+    },
+    InvalidPointVector {
+        description: "uncompressed x = p",
+        sec1: &[
+            0x04, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0xff, 0xff, 0xff, 0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e,
+            0xe7, 0xeb, 0x4a, 0x7c, 0x0f, 0x9e, 0x16, 0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e,
+            0xce, 0xcb, 0xb6, 0x40, 0x68, 0x37, 0xbf, 0x51, 0xf5,
+        ],
+    },
+    InvalidPointVector {
+        description: "uncompressed generator with y + 1",
+        sec1: &[
+            0x04, 0x6b, 0x17, 0xd1, 0xf2, 0xe1, 0x2c, 0x42, 0x47, 0xf8, 0xbc, 0xe6, 0xe5, 0x63,
+            0xa4, 0x40, 0xf2, 0x77, 0x03, 0x7d, 0x81, 0x2d, 0xeb, 0x33, 0xa0, 0xf4, 0xa1, 0x39,
+            0x45, 0xd8, 0x98, 0xc2, 0x96, 0x4f, 0xe3, 0x42, 0xe2, 0xfe, 0x1a, 0x7f, 0x9b, 0x8e,
+            0xe7, 0xeb, 0x4a, 0x7c, 0x0f, 0x9e, 0x16, 0x2b, 0xce, 0x33, 0x57, 0x6b, 0x31, 0x5e,
+            0xce, 0xcb, 0xb6, 0x40, 0x68, 0x37, 0xbf, 0x51, 0xf6,
+        ],
+    },
+];
+
+/// Define a test which checks that every [`InvalidPointVector`] in
+/// [`GENERIC_INVALID_POINTS`] and the given curve-specific vectors is
+/// rejected by the `VerifyingKey` constructors.
+///
+/// Requires the `verify` feature.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
+macro_rules! new_invalid_point_test {
+    ($curve:path, $vectors:expr) => {
+        #[test]
+        fn ecdsa_rejects_invalid_points() {
+            use $crate::{dev::GENERIC_INVALID_POINTS, EncodedPoint, VerifyingKey};
+
+            for vector in GENERIC_INVALID_POINTS.iter().chain($vectors.iter()) {
+                assert!(
+                    VerifyingKey::<$curve>::from_sec1_bytes(vector.sec1).is_err(),
+                    "accepted invalid point: {}",
+                    vector.description
+                );
+
+                if let Ok(point) = EncodedPoint::<$curve>::from_bytes(vector.sec1) {
+                    assert!(
+                        VerifyingKey::<$curve>::from_encoded_point(&point).is_err(),
+                        "accepted invalid point: {}",
+                        vector.description
+                    );
+                }
+            }
+        }
+    };
+}
+
 /// Define ECDSA signing test.
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
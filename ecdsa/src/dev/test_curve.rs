@@ -0,0 +1,869 @@
+//! Small elliptic curve with a working arithmetic implementation, for tests
+//! which need to compute signatures or verify them.
+//!
+//! The curve is 𝑦² = 𝑥³ - 3𝑥 + 147 over the field of integers modulo
+//! 2⁶⁴ - 189, and has prime order. Its 64-bit keys offer no security, and its
+//! arithmetic is neither constant time nor fast: it must never be used
+//! outside of tests.
+//!
+//! This module is hidden from the documentation and exempt from semver: it
+//! may change or be removed in any release.
+
+use crate::{
+    hazmat::{
+        self, bits2field, DigestPrimitive, EncodePrecomputedTables, FromDigest, LinearCombination,
+        PrecomputeVerifyPrimitive, PresignPrimitive, RecoverableSignPrimitive, VerifyPrimitive,
+    },
+    Error, NormalizeLow, RecoveryId, Result, Signature,
+};
+use core::{
+    borrow::Borrow,
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+use elliptic_curve::{
+    bigint::U64,
+    group::{
+        ff::{Field, PrimeField},
+        Group,
+    },
+    ops::Invert,
+    rand_core::RngCore,
+    sec1::{Coordinates, FromEncodedPoint, ToEncodedPoint},
+    subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption},
+    weierstrass,
+    zeroize::Zeroize,
+    AffineArithmetic, Curve, ProjectiveArithmetic, ScalarArithmetic,
+};
+use signature::digest::Digest;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Field modulus: 2⁶⁴ - 189
+const MODULUS: u64 = 0xffff_ffff_ffff_ff43;
+
+/// Order of the curve
+const ORDER: u64 = 0xffff_ffff_f411_0c1d;
+
+/// Coefficient 𝑏 of the curve equation
+const EQUATION_B: u64 = 147;
+
+/// Affine coordinates of the generator
+const GENERATOR: (u64, u64) = (4, 0xc8ff_9798_44ad_4580);
+
+/// Number of entries in [`PrecomputedTables`]
+const TABLE_SIZE: usize = 64;
+
+/// Version of the encoding of [`PrecomputedTables`]
+const TABLES_VERSION: u8 = 1;
+
+/// Test curve type.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub struct TestCurve;
+
+impl Curve for TestCurve {
+    type UInt = U64;
+
+    const ORDER: U64 = U64::from_u64(ORDER);
+}
+
+impl weierstrass::Curve for TestCurve {}
+
+impl weierstrass::PointCompression for TestCurve {
+    const COMPRESS_POINTS: bool = true;
+}
+
+impl AffineArithmetic for TestCurve {
+    type AffinePoint = AffinePoint;
+}
+
+impl ProjectiveArithmetic for TestCurve {
+    type ProjectivePoint = ProjectivePoint;
+}
+
+impl ScalarArithmetic for TestCurve {
+    type Scalar = Scalar;
+}
+
+impl DigestPrimitive for TestCurve {
+    type Digest = sha2::Sha256;
+}
+
+#[cfg(feature = "pkcs8")]
+impl elliptic_curve::AlgorithmParameters for TestCurve {
+    /// OID from the IANA arc reserved for documentation (RFC 5612)
+    const OID: elliptic_curve::pkcs8::ObjectIdentifier =
+        elliptic_curve::pkcs8::ObjectIdentifier::new("1.3.6.1.4.1.32473.1");
+}
+
+/// SEC1 encoded point.
+pub type EncodedPoint = elliptic_curve::sec1::EncodedPoint<TestCurve>;
+
+/// Field element bytes.
+pub type FieldBytes = elliptic_curve::FieldBytes<TestCurve>;
+
+/// ECDSA/TestCurve signing key.
+#[cfg(feature = "sign")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
+pub type SigningKey = crate::SigningKey<TestCurve>;
+
+/// ECDSA/TestCurve verification key.
+#[cfg(feature = "verify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+pub type VerifyingKey = crate::VerifyingKey<TestCurve>;
+
+/// Compute `a + b` modulo `m`, for `a, b < m`.
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((u128::from(a) + u128::from(b)) % u128::from(m)) as u64
+}
+
+/// Compute `a - b` modulo `m`, for `a, b < m`.
+fn sub_mod(a: u64, b: u64, m: u64) -> u64 {
+    add_mod(a, m - b, m)
+}
+
+/// Compute `a * b` modulo `m`.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((u128::from(a) * u128::from(b)) % u128::from(m)) as u64
+}
+
+/// Compute `base^exp` modulo `m`.
+fn pow_mod(base: u64, mut exp: u64, m: u64) -> u64 {
+    let (mut result, mut base) = (1, base);
+
+    while exp != 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Decode a big endian integer, rejecting values which are not less than
+/// `m`.
+fn decode_mod(bytes: &FieldBytes, m: u64) -> Option<u64> {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(bytes);
+    Some(u64::from_be_bytes(buf)).filter(|&n| n < m)
+}
+
+/// Implement the arithmetic operators for a type wrapping an integer modulo
+/// `$modulus`.
+macro_rules! impl_modular_ops {
+    ($ty:ident, $modulus:expr) => {
+        impl Add for $ty {
+            type Output = $ty;
+
+            fn add(self, rhs: $ty) -> $ty {
+                $ty(add_mod(self.0, rhs.0, $modulus))
+            }
+        }
+
+        impl Add<&$ty> for $ty {
+            type Output = $ty;
+
+            fn add(self, rhs: &$ty) -> $ty {
+                self + *rhs
+            }
+        }
+
+        impl AddAssign for $ty {
+            fn add_assign(&mut self, rhs: $ty) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl AddAssign<&$ty> for $ty {
+            fn add_assign(&mut self, rhs: &$ty) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = $ty;
+
+            fn sub(self, rhs: $ty) -> $ty {
+                $ty(sub_mod(self.0, rhs.0, $modulus))
+            }
+        }
+
+        impl Sub<&$ty> for $ty {
+            type Output = $ty;
+
+            fn sub(self, rhs: &$ty) -> $ty {
+                self - *rhs
+            }
+        }
+
+        impl SubAssign for $ty {
+            fn sub_assign(&mut self, rhs: $ty) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl SubAssign<&$ty> for $ty {
+            fn sub_assign(&mut self, rhs: &$ty) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl Mul for $ty {
+            type Output = $ty;
+
+            fn mul(self, rhs: $ty) -> $ty {
+                $ty(mul_mod(self.0, rhs.0, $modulus))
+            }
+        }
+
+        impl Mul<&$ty> for $ty {
+            type Output = $ty;
+
+            fn mul(self, rhs: &$ty) -> $ty {
+                self * *rhs
+            }
+        }
+
+        impl MulAssign for $ty {
+            fn mul_assign(&mut self, rhs: $ty) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl MulAssign<&$ty> for $ty {
+            fn mul_assign(&mut self, rhs: &$ty) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl Neg for $ty {
+            type Output = $ty;
+
+            fn neg(self) -> $ty {
+                $ty(sub_mod(0, self.0, $modulus))
+            }
+        }
+
+        impl ConditionallySelectable for $ty {
+            fn conditional_select(a: &$ty, b: &$ty, choice: Choice) -> $ty {
+                $ty(u64::conditional_select(&a.0, &b.0, choice))
+            }
+        }
+
+        impl ConstantTimeEq for $ty {
+            fn ct_eq(&self, other: &$ty) -> Choice {
+                self.0.ct_eq(&other.0)
+            }
+        }
+    };
+}
+
+/// Element of the base field.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct FieldElement(u64);
+
+impl_modular_ops!(FieldElement, MODULUS);
+
+impl FieldElement {
+    /// Compute the multiplicative inverse, which is zero for zero.
+    fn invert(self) -> Self {
+        FieldElement(pow_mod(self.0, MODULUS - 2, MODULUS))
+    }
+
+    /// Compute a square root, if one exists (using 𝑝 ≡ 3 mod 4).
+    fn sqrt(self) -> Option<Self> {
+        let root = FieldElement(pow_mod(self.0, (MODULUS + 1) / 4, MODULUS));
+        Some(root).filter(|root| *root * *root == self)
+    }
+
+    /// Compute 𝑥³ - 3𝑥 + 𝑏.
+    fn curve_equation(x: Self) -> Self {
+        x * x * x - x - x - x + FieldElement(EQUATION_B)
+    }
+
+    fn is_odd(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    fn to_bytes(self) -> FieldBytes {
+        self.0.to_be_bytes().into()
+    }
+}
+
+/// Scalar modulo the order of [`TestCurve`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Scalar(u64);
+
+impl_modular_ops!(Scalar, ORDER);
+
+impl Field for Scalar {
+    fn random(mut rng: impl RngCore) -> Self {
+        loop {
+            let candidate = rng.next_u64();
+
+            if candidate < ORDER {
+                return Scalar(candidate);
+            }
+        }
+    }
+
+    fn zero() -> Self {
+        Scalar(0)
+    }
+
+    fn one() -> Self {
+        Scalar(1)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    fn square(&self) -> Self {
+        *self * self
+    }
+
+    fn double(&self) -> Self {
+        *self + self
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        CtOption::new(Scalar(pow_mod(self.0, ORDER - 2, ORDER)), !self.0.ct_eq(&0))
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        // The order is 1 mod 4, so no simple square root formula applies and
+        // none of the tests need one
+        let _ = self;
+        CtOption::new(Scalar(0), Choice::from(0))
+    }
+}
+
+impl PrimeField for Scalar {
+    type Repr = FieldBytes;
+
+    const NUM_BITS: u32 = 64;
+    const CAPACITY: u32 = 63;
+    const S: u32 = 2;
+
+    fn from_repr(bytes: FieldBytes) -> Option<Self> {
+        decode_mod(&bytes, ORDER).map(Scalar)
+    }
+
+    fn to_repr(&self) -> FieldBytes {
+        self.0.to_be_bytes().into()
+    }
+
+    fn is_odd(&self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    fn multiplicative_generator() -> Self {
+        Scalar(2)
+    }
+
+    fn root_of_unity() -> Self {
+        Scalar(0x030b_8bb1_c44f_23bf)
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(n: u64) -> Scalar {
+        Scalar(n % ORDER)
+    }
+}
+
+impl From<Scalar> for FieldBytes {
+    fn from(scalar: Scalar) -> FieldBytes {
+        scalar.to_repr()
+    }
+}
+
+impl From<&Scalar> for FieldBytes {
+    fn from(scalar: &Scalar) -> FieldBytes {
+        scalar.to_repr()
+    }
+}
+
+impl Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl FromDigest<TestCurve> for Scalar {
+    fn from_digest<D>(digest: D) -> Self
+    where
+        D: Digest,
+    {
+        let n = u64::from_be_bytes(bits2field::<TestCurve>(&digest.finalize()).into());
+
+        // The order is greater than 2⁶³, so one subtraction reduces any input
+        Scalar(if n < ORDER { n } else { n - ORDER })
+    }
+}
+
+impl NormalizeLow for Scalar {
+    fn normalize_low(&self) -> (Self, bool) {
+        if self.0 > ORDER / 2 {
+            (-*self, true)
+        } else {
+            (*self, false)
+        }
+    }
+}
+
+impl RecoverableSignPrimitive<TestCurve> for Scalar {
+    fn try_sign_recoverable_prehashed<K: Borrow<Scalar> + Invert<Output = Scalar>>(
+        &self,
+        ephemeral_scalar: &K,
+        hashed_msg: &Scalar,
+    ) -> Result<(Signature<TestCurve>, bool)> {
+        self.try_sign_prehashed_with_recovery_id(ephemeral_scalar, hashed_msg)
+            .map(|(signature, recovery_id)| (signature, recovery_id.is_y_odd()))
+    }
+
+    fn try_sign_prehashed_with_recovery_id<K: Borrow<Scalar> + Invert<Output = Scalar>>(
+        &self,
+        ephemeral_scalar: &K,
+        hashed_msg: &Scalar,
+    ) -> Result<(Signature<TestCurve>, RecoveryId)> {
+        let k = ephemeral_scalar.borrow();
+        let k_inv = Option::<Scalar>::from(ephemeral_scalar.invert()).ok_or_else(Error::new)?;
+        let big_r = (ProjectivePoint::generator() * k).0;
+
+        // The order is less than the modulus, so this is `x mod n`
+        let is_x_reduced = big_r.x.0 >= ORDER;
+        let r = Scalar::from(big_r.x.0);
+        let s = k_inv * (*hashed_msg + r * self);
+
+        if r.is_zero() || s.is_zero() {
+            return Err(Error::new());
+        }
+
+        let signature = Signature::from_scalars(r, s)?;
+        Ok((signature, RecoveryId::new(big_r.y.is_odd(), is_x_reduced)))
+    }
+}
+
+impl PresignPrimitive<TestCurve> for Scalar {
+    fn try_compute_r(&self, ephemeral_scalar: &Scalar) -> Result<Scalar> {
+        let r = Scalar::from((ProjectivePoint::generator() * ephemeral_scalar).0.x.0);
+
+        if r.is_zero() {
+            return Err(Error::new());
+        }
+
+        Ok(r)
+    }
+
+    fn try_sign_prehashed_presigned(
+        &self,
+        ephemeral_scalar: &Scalar,
+        r: &Scalar,
+        hashed_msg: &Scalar,
+    ) -> Result<Signature<TestCurve>> {
+        let k_inv =
+            Option::<Scalar>::from(Field::invert(ephemeral_scalar)).ok_or_else(Error::new)?;
+        let s = k_inv * (*hashed_msg + *r * self);
+
+        if r.is_zero() || s.is_zero() {
+            return Err(Error::new());
+        }
+
+        Signature::from_scalars(*r, s)
+    }
+}
+
+/// Point in affine coordinates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AffinePoint {
+    x: FieldElement,
+    y: FieldElement,
+    infinity: bool,
+}
+
+impl AffinePoint {
+    /// Identity point.
+    pub const IDENTITY: Self = AffinePoint {
+        x: FieldElement(0),
+        y: FieldElement(0),
+        infinity: true,
+    };
+
+    /// Generator point.
+    pub const GENERATOR: Self = AffinePoint {
+        x: FieldElement(GENERATOR.0),
+        y: FieldElement(GENERATOR.1),
+        infinity: false,
+    };
+
+    fn add(&self, other: &Self) -> Self {
+        if self.infinity {
+            return *other;
+        }
+
+        if other.infinity {
+            return *self;
+        }
+
+        let lambda = if self.x == other.x {
+            if self.y == -other.y {
+                return Self::IDENTITY;
+            }
+
+            // Tangent slope: (3𝑥² - 3) / 2𝑦
+            let three_x_squared = self.x * self.x * FieldElement(3);
+            (three_x_squared - FieldElement(3)) * (self.y + self.y).invert()
+        } else {
+            (other.y - self.y) * (other.x - self.x).invert()
+        };
+
+        let x = lambda * lambda - self.x - other.x;
+        let y = lambda * (self.x - x) - self.y;
+
+        AffinePoint {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    fn neg(&self) -> Self {
+        AffinePoint {
+            y: -self.y,
+            ..*self
+        }
+    }
+}
+
+impl Default for AffinePoint {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl ConditionallySelectable for AffinePoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        AffinePoint {
+            x: FieldElement::conditional_select(&a.x, &b.x, choice),
+            y: FieldElement::conditional_select(&a.y, &b.y, choice),
+            infinity: u8::conditional_select(&(a.infinity as u8), &(b.infinity as u8), choice) == 1,
+        }
+    }
+}
+
+impl ConstantTimeEq for AffinePoint {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Choice::from((self == other) as u8)
+    }
+}
+
+impl FromEncodedPoint<TestCurve> for AffinePoint {
+    fn from_encoded_point(point: &EncodedPoint) -> Option<Self> {
+        let (x, y) = match point.coordinates() {
+            Coordinates::Identity => return Some(Self::IDENTITY),
+            Coordinates::Compressed { x, y_is_odd } => {
+                let x = FieldElement(decode_mod(x, MODULUS)?);
+                let y = FieldElement::curve_equation(x).sqrt()?;
+                (x, if y.is_odd() == y_is_odd { y } else { -y })
+            }
+            Coordinates::Uncompressed { x, y } => (
+                FieldElement(decode_mod(x, MODULUS)?),
+                FieldElement(decode_mod(y, MODULUS)?),
+            ),
+            Coordinates::Compact { .. } => return None,
+        };
+
+        if y * y != FieldElement::curve_equation(x) {
+            return None;
+        }
+
+        Some(AffinePoint {
+            x,
+            y,
+            infinity: false,
+        })
+    }
+}
+
+impl ToEncodedPoint<TestCurve> for AffinePoint {
+    fn to_encoded_point(&self, compress: bool) -> EncodedPoint {
+        if self.infinity {
+            EncodedPoint::identity()
+        } else {
+            EncodedPoint::from_affine_coordinates(&self.x.to_bytes(), &self.y.to_bytes(), compress)
+        }
+    }
+}
+
+impl VerifyPrimitive<TestCurve> for AffinePoint {
+    fn verify_prehashed(
+        &self,
+        hashed_msg: &Scalar,
+        signature: &Signature<TestCurve>,
+    ) -> Result<()> {
+        hazmat::verify_prehashed(self, hashed_msg, signature)
+    }
+}
+
+/// Multiples 2ⁱ×𝑸 of a public point 𝑸, for `i` in `0..64`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrecomputedTables([AffinePoint; TABLE_SIZE]);
+
+impl PrecomputeVerifyPrimitive<TestCurve> for AffinePoint {
+    type Tables = PrecomputedTables;
+
+    fn precompute(&self) -> PrecomputedTables {
+        let mut tables = [*self; TABLE_SIZE];
+
+        for i in 1..TABLE_SIZE {
+            tables[i] = tables[i - 1].add(&tables[i - 1]);
+        }
+
+        PrecomputedTables(tables)
+    }
+
+    fn verify_prehashed_precomputed(
+        &self,
+        tables: &PrecomputedTables,
+        hashed_msg: &Scalar,
+        signature: &Signature<TestCurve>,
+    ) -> Result<()> {
+        let (r, s) = signature.split_scalars();
+        let s_inv = Option::<Scalar>::from(s.invert()).ok_or_else(Error::new)?;
+        let u2 = (*r * s_inv).0;
+
+        let u2_q = tables
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| u2 >> i & 1 == 1)
+            .fold(AffinePoint::IDENTITY, |acc, (_, point)| acc.add(point));
+
+        let big_r = (ProjectivePoint::generator() * (*hashed_msg * s_inv))
+            .0
+            .add(&u2_q);
+
+        if !big_r.infinity && Scalar::from(big_r.x.0) == *r {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+}
+
+impl EncodePrecomputedTables<TestCurve> for AffinePoint {
+    const TABLES_VERSION: u8 = TABLES_VERSION;
+
+    #[cfg(feature = "alloc")]
+    fn encode_tables(tables: &PrecomputedTables) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(TABLE_SIZE * 16);
+
+        for point in tables.0.iter() {
+            bytes.extend_from_slice(&point.x.to_bytes());
+            bytes.extend_from_slice(&point.y.to_bytes());
+        }
+
+        bytes
+    }
+
+    fn decode_tables(&self, bytes: &[u8]) -> Result<PrecomputedTables> {
+        if bytes.len() != TABLE_SIZE * 16 {
+            return Err(Error::new());
+        }
+
+        let mut tables = [AffinePoint::IDENTITY; TABLE_SIZE];
+
+        for (point, chunk) in tables.iter_mut().zip(bytes.chunks(16)) {
+            let encoded = EncodedPoint::from_untagged_bytes(chunk.into());
+            *point = AffinePoint::from_encoded_point(&encoded).ok_or_else(Error::new)?;
+        }
+
+        // Each entry must be the double of the previous one, starting from
+        // this key
+        let tables = PrecomputedTables(tables);
+
+        if tables == self.precompute() {
+            Ok(tables)
+        } else {
+            Err(Error::new())
+        }
+    }
+}
+
+/// Point on [`TestCurve`], which is stored in affine coordinates (as
+/// performance is not a concern).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProjectivePoint(AffinePoint);
+
+impl From<AffinePoint> for ProjectivePoint {
+    fn from(point: AffinePoint) -> ProjectivePoint {
+        ProjectivePoint(point)
+    }
+}
+
+impl From<ProjectivePoint> for AffinePoint {
+    fn from(point: ProjectivePoint) -> AffinePoint {
+        point.0
+    }
+}
+
+impl ConditionallySelectable for ProjectivePoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        ProjectivePoint(AffinePoint::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ConstantTimeEq for ProjectivePoint {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl Group for ProjectivePoint {
+    type Scalar = Scalar;
+
+    fn random(rng: impl RngCore) -> Self {
+        Self::generator() * Scalar::random(rng)
+    }
+
+    fn identity() -> Self {
+        ProjectivePoint(AffinePoint::IDENTITY)
+    }
+
+    fn generator() -> Self {
+        ProjectivePoint(AffinePoint::GENERATOR)
+    }
+
+    fn is_identity(&self) -> Choice {
+        Choice::from(self.0.infinity as u8)
+    }
+
+    fn double(&self) -> Self {
+        ProjectivePoint(self.0.add(&self.0))
+    }
+}
+
+impl elliptic_curve::group::Curve for ProjectivePoint {
+    type AffineRepr = AffinePoint;
+
+    fn to_affine(&self) -> AffinePoint {
+        self.0
+    }
+}
+
+impl LinearCombination<TestCurve> for ProjectivePoint {}
+
+/// Implement an addition or subtraction operator for [`ProjectivePoint`]
+/// with the given right hand side type.
+macro_rules! impl_point_ops {
+    ($rhs:ty) => {
+        impl Add<$rhs> for ProjectivePoint {
+            type Output = ProjectivePoint;
+
+            #[allow(clippy::op_ref)]
+            fn add(self, rhs: $rhs) -> ProjectivePoint {
+                ProjectivePoint(self.0.add(&ProjectivePoint::from(rhs.clone()).0))
+            }
+        }
+
+        impl AddAssign<$rhs> for ProjectivePoint {
+            fn add_assign(&mut self, rhs: $rhs) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl Sub<$rhs> for ProjectivePoint {
+            type Output = ProjectivePoint;
+
+            fn sub(self, rhs: $rhs) -> ProjectivePoint {
+                ProjectivePoint(self.0.add(&ProjectivePoint::from(rhs.clone()).0.neg()))
+            }
+        }
+
+        impl SubAssign<$rhs> for ProjectivePoint {
+            fn sub_assign(&mut self, rhs: $rhs) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_point_ops!(ProjectivePoint);
+impl_point_ops!(&ProjectivePoint);
+impl_point_ops!(AffinePoint);
+impl_point_ops!(&AffinePoint);
+
+impl From<&ProjectivePoint> for ProjectivePoint {
+    fn from(point: &ProjectivePoint) -> ProjectivePoint {
+        *point
+    }
+}
+
+impl From<&AffinePoint> for ProjectivePoint {
+    fn from(point: &AffinePoint) -> ProjectivePoint {
+        ProjectivePoint(*point)
+    }
+}
+
+impl Neg for ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn neg(self) -> ProjectivePoint {
+        ProjectivePoint(self.0.neg())
+    }
+}
+
+impl Mul<Scalar> for ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn mul(self, scalar: Scalar) -> ProjectivePoint {
+        // Double-and-add, from the most significant bit
+        (0..64).rev().fold(ProjectivePoint::identity(), |acc, i| {
+            let acc = acc.double();
+
+            if scalar.0 >> i & 1 == 1 {
+                acc + self
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+impl Mul<&Scalar> for ProjectivePoint {
+    type Output = ProjectivePoint;
+
+    fn mul(self, scalar: &Scalar) -> ProjectivePoint {
+        self * *scalar
+    }
+}
+
+impl MulAssign<Scalar> for ProjectivePoint {
+    fn mul_assign(&mut self, scalar: Scalar) {
+        *self = *self * scalar;
+    }
+}
+
+impl MulAssign<&Scalar> for ProjectivePoint {
+    fn mul_assign(&mut self, scalar: &Scalar) {
+        *self = *self * scalar;
+    }
+}
+
+impl Sum for ProjectivePoint {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ProjectivePoint::identity(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ProjectivePoint> for ProjectivePoint {
+    fn sum<I: Iterator<Item = &'a ProjectivePoint>>(iter: I) -> Self {
+        iter.fold(ProjectivePoint::identity(), Add::add)
+    }
+}
@@ -0,0 +1,108 @@
+//! Support for [`did:key`] decentralized identifiers.
+//!
+//! [`did:key`]: https://w3c-ccg.github.io/did-method-key/
+
+use crate::{multibase::MulticodecParameters, Error, Result, VerifyingKey};
+use alloc::{format, string::String};
+use core::ops::Add;
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    sec1::{FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+    weierstrass::Curve,
+    AffinePoint, ProjectiveArithmetic,
+};
+
+/// Prefix of `did:key` identifiers.
+const DID_KEY_PREFIX: &str = "did:key:";
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + MulticodecParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Serialize this [`VerifyingKey`] as a `did:key` identifier, i.e.
+    /// `did:key:` followed by its [`VerifyingKey::to_multibase`] encoding.
+    pub fn to_did_key(&self) -> String {
+        format!("{}{}", DID_KEY_PREFIX, self.to_multibase())
+    }
+
+    /// Parse a [`VerifyingKey`] from a `did:key` identifier.
+    ///
+    /// A DID URL whose fragment is the key's own multibase encoding (i.e. the
+    /// verification method ID) is also accepted.
+    pub fn from_did_key(did: &str) -> Result<Self> {
+        let method_specific_id = did.strip_prefix(DID_KEY_PREFIX).ok_or_else(Error::new)?;
+
+        let multibase = match method_specific_id.find('#') {
+            Some(pos) if method_specific_id[(pos + 1)..] == method_specific_id[..pos] => {
+                &method_specific_id[..pos]
+            }
+            Some(_) => return Err(Error::new()),
+            None => method_specific_id,
+        };
+
+        Self::from_multibase(multibase)
+    }
+
+    /// Serialize this [`VerifyingKey`] as the JSON of a DID document
+    /// verification method of type `Multikey`, as found in the DID document
+    /// for its `did:key` identifier.
+    pub fn to_verification_method_json(&self) -> String {
+        let multibase = self.to_multibase();
+        let did = format!("{}{}", DID_KEY_PREFIX, multibase);
+
+        // All of the interpolated values are base58 or fixed strings, so no
+        // JSON escaping is required
+        format!(
+            r##"{{"id":"{did}#{multibase}","type":"Multikey","controller":"{did}","publicKeyMultibase":"{multibase}"}}"##,
+            did = did,
+            multibase = multibase
+        )
+    }
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use crate::VerifyingKey;
+    use elliptic_curve::dev::MockCurve;
+
+    /// `did:key` for the public key from RFC 6979 Appendix 2.5 (NIST P-256)
+    const DID_KEY: &str = "did:key:zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP";
+
+    /// Public key from RFC 6979 Appendix 2.5 (NIST P-256), compressed
+    const COMPRESSED_KEY_HEX: &str =
+        "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+
+    #[test]
+    fn did_key_roundtrip() {
+        let key = VerifyingKey::<MockCurve>::from_did_key(DID_KEY).unwrap();
+        assert_eq!(key.to_sec1_hex(true), COMPRESSED_KEY_HEX);
+        assert_eq!(key.to_did_key(), DID_KEY);
+    }
+
+    #[test]
+    fn did_url_fragment() {
+        let multibase = &DID_KEY[8..];
+        let did_url = [DID_KEY, "#", multibase].concat();
+        assert!(VerifyingKey::<MockCurve>::from_did_key(&did_url).is_ok());
+        assert!(VerifyingKey::<MockCurve>::from_did_key(&[DID_KEY, "#key-1"].concat()).is_err());
+        assert!(VerifyingKey::<MockCurve>::from_did_key(multibase).is_err());
+    }
+
+    #[test]
+    fn verification_method_json() {
+        let key = VerifyingKey::<MockCurve>::from_did_key(DID_KEY).unwrap();
+        assert_eq!(
+            key.to_verification_method_json(),
+            concat!(
+                r#"{"id":"did:key:zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP"#,
+                r#"#zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP","type":"Multikey","#,
+                r#""controller":"did:key:zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP","#,
+                r#""publicKeyMultibase":"zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP"}"#
+            )
+        );
+    }
+}
@@ -0,0 +1,341 @@
+//! DNS resource records for publishing ECDSA public keys:
+//!
+//! - `TLSA` records for DNS-Based Authentication of Named Entities (DANE)
+//!   as described in [RFC 6698]
+//! - `SSHFP` records for SSH host keys as described in [RFC 4255] and
+//!   [RFC 6594]
+//!
+//! Records are serialized either as their wire format RDATA, or in the
+//! presentation format used by zone files via [`fmt::Display`].
+//!
+//! DNSSEC public keys (as in `DNSKEY` records) and signatures (as in `RRSIG`
+//! records) can also be converted to and from the wire format described in
+//! [RFC 6605].
+//!
+//! [RFC 6698]: https://tools.ietf.org/html/rfc6698
+//! [RFC 6605]: https://tools.ietf.org/html/rfc6605#section-4
+//! [RFC 4255]: https://tools.ietf.org/html/rfc4255
+//! [RFC 6594]: https://tools.ietf.org/html/rfc6594
+
+use crate::{
+    ssh::SshParameters, Error, Result, Signature, SignatureBytes, SignatureSize, VerifyingKey,
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    convert::{TryFrom, TryInto},
+    fmt,
+    ops::Add,
+};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::{ArrayLength, GenericArray},
+    pkcs8::{self, der::Encodable},
+    sec1::{
+        EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize,
+    },
+    weierstrass::Curve,
+    AffinePoint, AlgorithmParameters, ProjectiveArithmetic,
+};
+use sha2::{Digest, Sha256, Sha512};
+
+/// `TLSA` selector for the certificate's `SubjectPublicKeyInfo`.
+///
+/// This is the only selector which can be computed from a public key alone.
+const TLSA_SELECTOR_SPKI: u8 = 1;
+
+/// `SSHFP` algorithm number for ECDSA keys.
+const SSHFP_ALGORITHM_ECDSA: u8 = 3;
+
+/// `SSHFP` fingerprint type for SHA-256.
+const SSHFP_FINGERPRINT_SHA256: u8 = 2;
+
+/// `TLSA` certificate usage field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum TlsaCertUsage {
+    /// CA constraint (`PKIX-TA`)
+    PkixTa = 0,
+
+    /// Service certificate constraint (`PKIX-EE`)
+    PkixEe = 1,
+
+    /// Trust anchor assertion (`DANE-TA`)
+    DaneTa = 2,
+
+    /// Domain-issued certificate (`DANE-EE`)
+    DaneEe = 3,
+}
+
+/// `TLSA` matching type field.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum TlsaMatchingType {
+    /// Exact match on the selected content
+    Full = 0,
+
+    /// SHA-256 hash of the selected content
+    Sha256 = 1,
+
+    /// SHA-512 hash of the selected content
+    Sha512 = 2,
+}
+
+/// `TLSA` record for the `SubjectPublicKeyInfo` of a key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TlsaRecord {
+    /// Certificate usage
+    pub cert_usage: TlsaCertUsage,
+
+    /// Matching type
+    pub matching_type: TlsaMatchingType,
+
+    /// Certificate association data
+    pub data: Vec<u8>,
+}
+
+impl TlsaRecord {
+    /// Get the selector for this record, which is always the
+    /// `SubjectPublicKeyInfo` (1).
+    pub fn selector(&self) -> u8 {
+        TLSA_SELECTOR_SPKI
+    }
+
+    /// Serialize this record's RDATA in wire format.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = Vec::with_capacity(3 + self.data.len());
+        rdata.push(self.cert_usage as u8);
+        rdata.push(self.selector());
+        rdata.push(self.matching_type as u8);
+        rdata.extend_from_slice(&self.data);
+        rdata
+    }
+}
+
+/// Presentation format, e.g. `3 1 1 <hex>`.
+impl fmt::Display for TlsaRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} ",
+            self.cert_usage as u8,
+            self.selector(),
+            self.matching_type as u8
+        )?;
+        write_hex(f, &self.data)
+    }
+}
+
+/// `SSHFP` record containing the SHA-256 fingerprint of an SSH host key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SshfpRecord {
+    /// Fingerprint of the SSH public key blob
+    pub fingerprint: [u8; 32],
+}
+
+impl SshfpRecord {
+    /// Get the algorithm number for this record, which is always ECDSA (3).
+    pub fn algorithm(&self) -> u8 {
+        SSHFP_ALGORITHM_ECDSA
+    }
+
+    /// Get the fingerprint type for this record, which is always SHA-256 (2).
+    pub fn fingerprint_type(&self) -> u8 {
+        SSHFP_FINGERPRINT_SHA256
+    }
+
+    /// Serialize this record's RDATA in wire format.
+    pub fn to_rdata(&self) -> Vec<u8> {
+        let mut rdata = Vec::with_capacity(2 + self.fingerprint.len());
+        rdata.push(self.algorithm());
+        rdata.push(self.fingerprint_type());
+        rdata.extend_from_slice(&self.fingerprint);
+        rdata
+    }
+}
+
+/// Presentation format, e.g. `3 2 <hex>`.
+impl fmt::Display for SshfpRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ", self.algorithm(), self.fingerprint_type())?;
+        write_hex(f, &self.fingerprint)
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + AlgorithmParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Compute a `TLSA` record matching this key's `SubjectPublicKeyInfo`.
+    pub fn to_tlsa_record(
+        &self,
+        cert_usage: TlsaCertUsage,
+        matching_type: TlsaMatchingType,
+    ) -> Result<TlsaRecord> {
+        let public_key_bytes = self.to_encoded_point(false);
+        let spki = pkcs8::SubjectPublicKeyInfo {
+            algorithm: C::algorithm_identifier(),
+            subject_public_key: public_key_bytes.as_ref(),
+        };
+
+        let spki_len = spki
+            .encoded_len()
+            .and_then(TryInto::try_into)
+            .map_err(|_| Error::new())?;
+
+        let mut spki_der = vec![0u8; spki_len];
+        spki.encode_to_slice(&mut spki_der)
+            .map_err(|_| Error::new())?;
+
+        let data = match matching_type {
+            TlsaMatchingType::Full => spki_der,
+            TlsaMatchingType::Sha256 => Sha256::digest(&spki_der).to_vec(),
+            TlsaMatchingType::Sha512 => Sha512::digest(&spki_der).to_vec(),
+        };
+
+        Ok(TlsaRecord {
+            cert_usage,
+            matching_type,
+            data,
+        })
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`VerifyingKey`] from the public key field of a DNSSEC
+    /// `DNSKEY` record, i.e. the concatenated x and y coordinates of the
+    /// point without a SEC1 tag.
+    pub fn from_dnskey_public_key(public_key: &[u8]) -> Result<Self> {
+        let untagged =
+            GenericArray::<u8, UntaggedPointSize<C>>::from_exact_iter(public_key.iter().copied())
+                .ok_or_else(Error::new)?;
+
+        Self::from_encoded_point(&EncodedPoint::from_untagged_bytes(&untagged))
+    }
+
+    /// Serialize this [`VerifyingKey`] as the public key field of a DNSSEC
+    /// `DNSKEY` record.
+    pub fn to_dnskey_public_key(&self) -> Vec<u8> {
+        self.to_encoded_point(false).as_bytes()[1..].to_vec()
+    }
+}
+
+impl<C> Signature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Parse a signature from the signature field of a DNSSEC `RRSIG`
+    /// record, i.e. the big endian `r` and `s` scalars concatenated.
+    pub fn from_rrsig_signature(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+
+    /// Serialize this signature as the signature field of a DNSSEC `RRSIG`
+    /// record.
+    pub fn to_rrsig_signature(&self) -> SignatureBytes<C> {
+        self.bytes.clone()
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + SshParameters,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Compute an `SSHFP` record for this key when used as an SSH host key.
+    pub fn to_sshfp_record(&self) -> SshfpRecord {
+        SshfpRecord {
+            fingerprint: Sha256::digest(&self.to_ssh_public_key_blob()).into(),
+        }
+    }
+}
+
+/// Write the given bytes as lower case hexadecimal.
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    for byte in bytes {
+        write!(f, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{TlsaCertUsage, TlsaMatchingType};
+    use crate::VerifyingKey;
+    use alloc::string::ToString;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    /// Public key from RFC 6979 Appendix A.2.5 (P-256)
+    const UNCOMPRESSED_KEY: [u8; 65] = hex!(
+        "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6
+         7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+    );
+
+    fn verifying_key() -> VerifyingKey<MockCurve> {
+        VerifyingKey::from_sec1_bytes(&UNCOMPRESSED_KEY).unwrap()
+    }
+
+    #[test]
+    fn tlsa_full_spki() {
+        let record = verifying_key()
+            .to_tlsa_record(TlsaCertUsage::DaneEe, TlsaMatchingType::Full)
+            .unwrap();
+
+        assert_eq!(
+            &record.data[..26],
+            &hex!("3059301306072a8648ce3d020106082a8648ce3d030107034200")
+        );
+        assert_eq!(&record.data[26..], &UNCOMPRESSED_KEY[..]);
+        assert_eq!(&record.to_rdata()[..3], &[3, 1, 0]);
+    }
+
+    #[test]
+    fn tlsa_sha256_presentation_format() {
+        let record = verifying_key()
+            .to_tlsa_record(TlsaCertUsage::DaneEe, TlsaMatchingType::Sha256)
+            .unwrap();
+
+        assert_eq!(
+            record.to_string(),
+            "3 1 1 5a7a78cca4a0f420d9bc62bb669c3c2759e39f723d3ae10dcbe0f0815a07ecd4"
+        );
+    }
+
+    #[test]
+    fn dnskey_public_key() {
+        let public_key = verifying_key().to_dnskey_public_key();
+        assert_eq!(public_key, &UNCOMPRESSED_KEY[1..]);
+
+        let key = VerifyingKey::<MockCurve>::from_dnskey_public_key(&public_key).unwrap();
+        assert_eq!(
+            key.to_encoded_point(false).as_bytes(),
+            &UNCOMPRESSED_KEY[..]
+        );
+
+        assert!(VerifyingKey::<MockCurve>::from_dnskey_public_key(&UNCOMPRESSED_KEY).is_err());
+        assert!(VerifyingKey::<MockCurve>::from_dnskey_public_key(&public_key[1..]).is_err());
+    }
+
+    #[test]
+    fn sshfp_presentation_format() {
+        let record = verifying_key().to_sshfp_record();
+
+        assert_eq!(
+            record.to_string(),
+            "3 2 85fb8d5a68c862fb01199e9da422d34c011ae4bc5b6400511c7568ca7031165a"
+        );
+        assert_eq!(record.to_rdata().len(), 34);
+    }
+}
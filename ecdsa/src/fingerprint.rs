@@ -0,0 +1,124 @@
+//! Stable public key fingerprints.
+//!
+//! Fingerprints are encoded as a [multihash] of the key's canonical encoding:
+//!
+//! - 1-byte: multicodec identifier for SHA-256 (`0x12`)
+//! - 1-byte: digest length (`0x20`)
+//! - 32-bytes: SHA-256 digest of the canonical public key encoding
+//!
+//! For ECDSA the canonical public key encoding is the compressed SEC1 point.
+//! Other key types can produce fingerprints in the same format using
+//! [`Fingerprint::new`] with their own canonical encoding, e.g. the 32-byte
+//! compressed Edwards point for Ed25519 public keys.
+//!
+//! [multihash]: https://multiformats.io/multihash/
+
+use core::fmt;
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "verify")]
+use {
+    crate::VerifyingKey,
+    core::ops::Add,
+    elliptic_curve::{
+        consts::U1,
+        generic_array::ArrayLength,
+        sec1::{ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+        weierstrass::Curve,
+        AffinePoint, ProjectiveArithmetic,
+    },
+};
+
+/// Multicodec identifier for SHA-256.
+const SHA2_256_CODE: u8 = 0x12;
+
+/// Size of a SHA-256 digest.
+const SHA2_256_SIZE: usize = 32;
+
+/// Size of a serialized [`Fingerprint`].
+pub const FINGERPRINT_SIZE: usize = 2 + SHA2_256_SIZE;
+
+/// Public key fingerprint: SHA-256 multihash of a canonical key encoding.
+#[derive(Copy, Clone, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct Fingerprint([u8; FINGERPRINT_SIZE]);
+
+impl Fingerprint {
+    /// Compute the fingerprint of the given canonical public key encoding.
+    pub fn new(canonical_encoding: &[u8]) -> Self {
+        let mut bytes = [0u8; FINGERPRINT_SIZE];
+        bytes[0] = SHA2_256_CODE;
+        bytes[1] = SHA2_256_SIZE as u8;
+        bytes[2..].copy_from_slice(&Sha256::digest(canonical_encoding));
+        Fingerprint(bytes)
+    }
+
+    /// Borrow the serialized multihash.
+    pub fn as_bytes(&self) -> &[u8; FINGERPRINT_SIZE] {
+        &self.0
+    }
+
+    /// Borrow the SHA-256 digest, excluding the multihash prefix.
+    pub fn digest(&self) -> &[u8] {
+        &self.0[2..]
+    }
+}
+
+impl AsRef<[u8]> for Fingerprint {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fingerprint({})", self)
+    }
+}
+
+/// Lower case hexadecimal serialization of the multihash.
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "verify")]
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Compute the [`Fingerprint`] of this key's compressed SEC1 encoding.
+    pub fn fingerprint(&self) -> Fingerprint {
+        Fingerprint::new(self.inner.as_affine().to_encoded_point(true).as_bytes())
+    }
+}
+
+#[cfg(all(test, feature = "verify"))]
+mod tests {
+    use crate::VerifyingKey;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    const COMPRESSED_KEY: [u8; 33] =
+        hex!("0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6");
+
+    #[test]
+    fn verifying_key_fingerprint() {
+        let key = VerifyingKey::<MockCurve>::from_sec1_bytes(&COMPRESSED_KEY).unwrap();
+        let fingerprint = key.fingerprint();
+
+        assert_eq!(&fingerprint.as_bytes()[..2], &[0x12, 0x20]);
+        assert_eq!(fingerprint, super::Fingerprint::new(&COMPRESSED_KEY));
+        assert_eq!(
+            fingerprint.digest(),
+            hex!("a468072bf83a2703085af2570d847c88c93d8071175df0587bff53eb4cf57824")
+        );
+    }
+}
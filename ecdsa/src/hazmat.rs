@@ -13,11 +13,25 @@
 
 #[cfg(feature = "arithmetic")]
 use {
-    crate::{Result, SignatureSize},
+    crate::{RecoveryId, Result, SignatureSize},
     core::borrow::Borrow,
-    elliptic_curve::{ops::Invert, ProjectiveArithmetic, Scalar},
+    core::ops::Add,
+    elliptic_curve::{
+        consts::U1,
+        group::{
+            ff::{Field, PrimeField},
+            Curve as _, Group,
+        },
+        ops::Invert,
+        sec1::{ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+        subtle::{ConditionallySelectable, ConstantTimeEq, CtOption},
+        AffinePoint, ProjectiveArithmetic, ProjectivePoint, Scalar,
+    },
 };
 
+#[cfg(all(feature = "alloc", feature = "arithmetic"))]
+use alloc::vec::Vec;
+
 #[cfg(feature = "digest")]
 use {
     crate::signature::{digest::Digest, PrehashSignature},
@@ -25,10 +39,9 @@ use {
 };
 
 #[cfg(any(feature = "arithmetic", feature = "digest"))]
-use crate::{
-    elliptic_curve::{generic_array::ArrayLength, weierstrass::Curve},
-    Signature,
-};
+use crate::{elliptic_curve::generic_array::ArrayLength, Signature};
+
+use elliptic_curve::{bigint::ArrayEncoding as _, weierstrass::Curve, FieldBytes};
 
 /// Try to sign the given prehashed message using ECDSA.
 ///
@@ -54,6 +67,31 @@ where
         ephemeral_scalar: &K,
         hashed_msg: &Scalar<C>,
     ) -> Result<Signature<C>>;
+
+    /// Try to sign a batch of prehashed messages.
+    ///
+    /// Accepts a slice of `(ephemeral_scalar, hashed_msg)` pairs, each of
+    /// which carry the same requirements as the arguments to
+    /// [`SignPrimitive::try_sign_prehashed`].
+    ///
+    /// The default implementation signs each message individually.
+    /// Implementations can override it to amortize per-signature costs.
+    /// Types which implement [`RecoverableSignPrimitive`] override
+    /// [`RecoverableSignPrimitive::try_sign_recoverable_prehashed_batch`]
+    /// instead.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn try_sign_prehashed_batch<K: Borrow<Scalar<C>> + Invert<Output = Scalar<C>>>(
+        &self,
+        batch: &[(K, Scalar<C>)],
+    ) -> Result<Vec<Signature<C>>> {
+        batch
+            .iter()
+            .map(|(ephemeral_scalar, hashed_msg)| {
+                self.try_sign_prehashed(ephemeral_scalar, hashed_msg)
+            })
+            .collect()
+    }
 }
 
 /// [`SignPrimitive`] for signature implementations that can provide public key
@@ -76,6 +114,49 @@ where
         ephemeral_scalar: &K,
         hashed_msg: &Scalar<C>,
     ) -> Result<(Signature<C>, bool)>;
+
+    /// Try to sign the prehashed message, returning the [`RecoveryId`]
+    /// describing the computed 𝐑 = 𝑘×𝑮 point, i.e. whether its
+    /// y-coordinate is odd and whether its x-coordinate was reduced modulo
+    /// the curve order to obtain `r`.
+    ///
+    /// Accepts the same arguments as [`SignPrimitive::try_sign_prehashed`].
+    ///
+    /// The provided implementation calls
+    /// [`RecoverableSignPrimitive::try_sign_recoverable_prehashed`] and
+    /// reports that `r` was not reduced, which is wrong with negligible
+    /// probability for curves whose order is close to the field modulus.
+    /// Implementations should override it to report the reduction.
+    fn try_sign_prehashed_with_recovery_id<K: Borrow<Scalar<C>> + Invert<Output = Scalar<C>>>(
+        &self,
+        ephemeral_scalar: &K,
+        hashed_msg: &Scalar<C>,
+    ) -> Result<(Signature<C>, RecoveryId)> {
+        self.try_sign_recoverable_prehashed(ephemeral_scalar, hashed_msg)
+            .map(|(signature, is_y_odd)| (signature, RecoveryId::new(is_y_odd, false)))
+    }
+
+    /// Try to sign a batch of prehashed messages.
+    ///
+    /// Accepts the same arguments as [`SignPrimitive::try_sign_prehashed_batch`]
+    /// but returns the same boolean flag as
+    /// [`RecoverableSignPrimitive::try_sign_recoverable_prehashed`] for each
+    /// signature.
+    ///
+    /// The default implementation signs each message individually.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn try_sign_recoverable_prehashed_batch<K: Borrow<Scalar<C>> + Invert<Output = Scalar<C>>>(
+        &self,
+        batch: &[(K, Scalar<C>)],
+    ) -> Result<Vec<(Signature<C>, bool)>> {
+        batch
+            .iter()
+            .map(|(ephemeral_scalar, hashed_msg)| {
+                self.try_sign_recoverable_prehashed(ephemeral_scalar, hashed_msg)
+            })
+            .collect()
+    }
 }
 
 #[cfg(feature = "arithmetic")]
@@ -93,6 +174,138 @@ where
         self.try_sign_recoverable_prehashed(ephemeral_scalar, hashed_msg)
             .map(|res| res.0)
     }
+
+    #[cfg(feature = "alloc")]
+    fn try_sign_prehashed_batch<K: Borrow<Scalar<C>> + Invert<Output = Scalar<C>>>(
+        &self,
+        batch: &[(K, Scalar<C>)],
+    ) -> Result<Vec<Signature<C>>> {
+        self.try_sign_recoverable_prehashed_batch(batch)
+            .map(|res| res.into_iter().map(|(signature, _)| signature).collect())
+    }
+}
+
+/// [`SignPrimitive`] for implementations which can use precomputed tables
+/// (e.g. of multiples of the generator) to reduce the latency of computing
+/// 𝐑 = 𝑘×𝑮.
+///
+/// Tables are computed once using [`PrecomputeSignPrimitive::precompute`],
+/// typically after the first use of a long-lived key, and then passed to each
+/// subsequent signing operation.
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait PrecomputeSignPrimitive<C>: SignPrimitive<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Precomputed tables used when signing.
+    type Tables;
+
+    /// Compute the tables used to accelerate signing with this key.
+    fn precompute(&self) -> Self::Tables;
+
+    /// Try to sign the prehashed message using the given precomputed tables.
+    ///
+    /// Accepts the same arguments as [`SignPrimitive::try_sign_prehashed`],
+    /// in addition to tables previously computed for this key.
+    fn try_sign_prehashed_precomputed<K: Borrow<Scalar<C>> + Invert<Output = Scalar<C>>>(
+        &self,
+        tables: &Self::Tables,
+        ephemeral_scalar: &K,
+        hashed_msg: &Scalar<C>,
+    ) -> Result<Signature<C>>;
+}
+
+/// [`SignPrimitive`] for implementations which can split signing into an
+/// offline phase, which computes 𝐑 = 𝑘×𝑮 before the message is known, and
+/// an online phase which only requires scalar arithmetic.
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait PresignPrimitive<C>: SignPrimitive<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Compute the `r` component of a signature using the given ephemeral
+    /// scalar, i.e. the x-coordinate of 𝐑 = 𝑘×𝑮 reduced modulo the curve
+    /// order.
+    ///
+    /// Accepts `ephemeral_scalar` with the same requirements as
+    /// [`SignPrimitive::try_sign_prehashed`]. Returns an error if `r` is
+    /// zero.
+    fn try_compute_r(&self, ephemeral_scalar: &Scalar<C>) -> Result<Scalar<C>>;
+
+    /// Try to sign the prehashed message using an ephemeral scalar and the
+    /// `r` previously computed from it by [`PresignPrimitive::try_compute_r`].
+    ///
+    /// Accepts the same arguments as [`SignPrimitive::try_sign_prehashed`].
+    /// Each `(ephemeral_scalar, r)` pair MUST BE USED AT MOST ONCE!!!
+    fn try_sign_prehashed_presigned(
+        &self,
+        ephemeral_scalar: &Scalar<C>,
+        r: &Scalar<C>,
+        hashed_msg: &Scalar<C>,
+    ) -> Result<Signature<C>>;
+}
+
+/// [`SignPrimitive`] for implementations which support masking intermediate
+/// values with a random blinding factor, reducing side-channel leakage.
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait BlindedSignPrimitive<C>: SignPrimitive<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Try to sign the prehashed message, masking the computation with the
+    /// given `blinding_factor`, which MUST BE UNIFORMLY RANDOM AND NONZERO!!!
+    ///
+    /// Accepts the same arguments as [`SignPrimitive::try_sign_prehashed`].
+    ///
+    /// The default implementation only blinds the inversion of `k`, computing
+    /// it as `(k × b)⁻¹ × b`. Implementations with access to the secret
+    /// scalar should override it to also blind the computation of `r × d`.
+    fn try_sign_prehashed_blinded(
+        &self,
+        ephemeral_scalar: &Scalar<C>,
+        hashed_msg: &Scalar<C>,
+        blinding_factor: &Scalar<C>,
+    ) -> Result<Signature<C>> {
+        let k = BlindedInversion {
+            ephemeral_scalar,
+            blinding_factor,
+        };
+
+        self.try_sign_prehashed(&k, hashed_msg)
+    }
+}
+
+/// Ephemeral scalar which is inverted using a blinding factor.
+#[cfg(feature = "arithmetic")]
+struct BlindedInversion<'a, S> {
+    ephemeral_scalar: &'a S,
+    blinding_factor: &'a S,
+}
+
+#[cfg(feature = "arithmetic")]
+impl<S> Borrow<S> for BlindedInversion<'_, S> {
+    fn borrow(&self) -> &S {
+        self.ephemeral_scalar
+    }
+}
+
+#[cfg(feature = "arithmetic")]
+impl<S: Field> Invert for BlindedInversion<'_, S> {
+    type Output = S;
+
+    fn invert(&self) -> CtOption<S> {
+        let blinding_factor = *self.blinding_factor;
+
+        (*self.ephemeral_scalar * blinding_factor)
+            .invert()
+            .map(|inverse| inverse * blinding_factor)
+    }
 }
 
 /// Verify the given prehashed message using ECDSA.
@@ -116,6 +329,158 @@ where
     fn verify_prehashed(&self, hashed_msg: &Scalar<C>, signature: &Signature<C>) -> Result<()>;
 }
 
+/// Compute the linear combination 𝑥×𝑘 + 𝑦×𝑙 of two points, as needed to
+/// verify signatures.
+///
+/// The provided implementation uses Shamir's trick (simultaneous
+/// double-scalar multiplication), sharing a single chain of point doublings
+/// between both scalars rather than computing each product separately.
+/// Curve crates with faster multi-scalar multiplication (e.g. interleaved
+/// wNAF, or an endomorphism) can override it.
+///
+/// Scalars are assumed to have a big endian [`PrimeField::Repr`], as is the
+/// case for [`FieldBytes`].
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait LinearCombination<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    /// Compute 𝑥×𝑘 + 𝑦×𝑙.
+    fn lincomb(
+        x: &ProjectivePoint<C>,
+        k: &Scalar<C>,
+        y: &ProjectivePoint<C>,
+        l: &Scalar<C>,
+    ) -> ProjectivePoint<C> {
+        let table = [ProjectivePoint::<C>::identity(), *x, *y, *x + y];
+        let mut result = ProjectivePoint::<C>::identity();
+
+        for (k_byte, l_byte) in k.to_repr().iter().zip(l.to_repr().iter()) {
+            for bit in (0..8).rev() {
+                let index = ((k_byte >> bit) & 1) | (((l_byte >> bit) & 1) << 1);
+
+                // Select the point to add without branching on the scalars
+                let addend = table.iter().zip(0u8..).fold(
+                    ProjectivePoint::<C>::identity(),
+                    |addend, (point, i)| {
+                        ProjectivePoint::<C>::conditional_select(&addend, point, i.ct_eq(&index))
+                    },
+                );
+
+                result = result.double() + addend;
+            }
+        }
+
+        result
+    }
+}
+
+/// Verify the prehashed message against the provided signature using the
+/// public key `q`, computing 𝑢₁×𝑮 + 𝑢₂×𝑸 with [`LinearCombination`].
+///
+/// Curve crates without an optimized verification routine can use this to
+/// implement [`VerifyPrimitive::verify_prehashed`].
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub fn verify_prehashed<C>(
+    q: &AffinePoint<C>,
+    hashed_msg: &Scalar<C>,
+    signature: &Signature<C>,
+) -> Result<()>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    ProjectivePoint<C>: LinearCombination<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    let (r, s) = signature.split_scalars();
+    let s_inv = Option::<Scalar<C>>::from(s.invert()).ok_or_else(crate::Error::new)?;
+    let u1 = *hashed_msg * s_inv;
+    let u2 = *r * s_inv;
+
+    let big_r = ProjectivePoint::<C>::lincomb(
+        &ProjectivePoint::<C>::generator(),
+        &u1,
+        &ProjectivePoint::<C>::from(*q),
+        &u2,
+    )
+    .to_affine()
+    .to_encoded_point(false);
+
+    // The x-coordinate is absent if 𝐑 is the identity
+    let x = big_r.x().ok_or_else(crate::Error::new)?;
+
+    if reduce_field_bytes::<C>(x) == *r {
+        Ok(())
+    } else {
+        Err(crate::Error::new())
+    }
+}
+
+/// [`VerifyPrimitive`] for implementations which can use precomputed tables
+/// of multiples of the public point (e.g. fixed windows or wNAF) to speed up
+/// repeated verification with the same key.
+///
+/// Tables are computed once using [`PrecomputeVerifyPrimitive::precompute`],
+/// typically for long-lived keys such as certificate authority keys, and then
+/// passed to each subsequent verification.
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait PrecomputeVerifyPrimitive<C>: VerifyPrimitive<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Precomputed tables used when verifying.
+    type Tables;
+
+    /// Compute the tables used to accelerate verification with this key.
+    fn precompute(&self) -> Self::Tables;
+
+    /// Verify the prehashed message against the provided signature using the
+    /// given precomputed tables.
+    ///
+    /// Accepts the same arguments as [`VerifyPrimitive::verify_prehashed`],
+    /// in addition to tables previously computed for this key.
+    fn verify_prehashed_precomputed(
+        &self,
+        tables: &Self::Tables,
+        hashed_msg: &Scalar<C>,
+        signature: &Signature<C>,
+    ) -> Result<()>;
+}
+
+/// [`PrecomputeVerifyPrimitive`] whose tables can be serialized, e.g. so they
+/// can be computed ahead of time and stored in flash rather than computed at
+/// boot.
+#[cfg(feature = "arithmetic")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arithmetic")))]
+pub trait EncodePrecomputedTables<C>: PrecomputeVerifyPrimitive<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Version of the encoding of [`PrecomputeVerifyPrimitive::Tables`],
+    /// which must change whenever the encoding does.
+    const TABLES_VERSION: u8;
+
+    /// Serialize the given tables.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    fn encode_tables(tables: &Self::Tables) -> Vec<u8>;
+
+    /// Deserialize tables computed for this key.
+    ///
+    /// Implementations must reject input which is not a complete encoding,
+    /// as well as tables which were not computed for this key (e.g. by
+    /// recomputing some of the entries): verifying with such tables could
+    /// accept invalid signatures.
+    fn decode_tables(&self, bytes: &[u8]) -> Result<Self::Tables>;
+}
+
 /// Bind a preferred [`Digest`] algorithm to an elliptic curve type.
 ///
 /// Generally there is a preferred variety of the SHA-2 family used with ECDSA
@@ -140,6 +505,10 @@ pub trait DigestPrimitive: Curve {
 /// which is compatible with the rules for calculating `h` from `H(M)` set out
 /// in RFC6979 section 2.4. This conversion cannot fail.
 ///
+/// Digests of any size are accepted: implementations should first convert the
+/// digest output to an integer using [`bits2field`], then reduce it modulo
+/// the curve order.
+///
 /// This trait may also be useful for other hash-to-scalar or hash-to-curve
 /// use cases.
 #[cfg(feature = "digest")]
@@ -148,7 +517,86 @@ pub trait FromDigest<C: Curve> {
     /// Instantiate this type from a [`Digest`] instance
     fn from_digest<D>(digest: D) -> Self
     where
-        D: Digest<OutputSize = FieldSize<C>>;
+        D: Digest;
+}
+
+/// Convert a bit string (e.g. a message digest) to a big endian integer
+/// serialized as [`FieldBytes`], as described by `bits2int` in
+/// [RFC 6979 Section 2.3.2] and SEC1 Section 4.1.3.
+///
+/// If the input is longer than the bit length of the curve order, only the
+/// leftmost bits are kept. Shorter inputs are left-padded with zeros.
+///
+/// The result is less than `2^qlen` (where `qlen` is the bit length of the
+/// order) but is not reduced modulo the order.
+///
+/// [RFC 6979 Section 2.3.2]: https://tools.ietf.org/html/rfc6979#section-2.3.2
+#[deny(clippy::indexing_slicing, clippy::panic)]
+pub fn bits2field<C: Curve>(bits: &[u8]) -> FieldBytes<C> {
+    let order = C::ORDER.to_be_byte_array();
+    let zero_bytes = order.iter().take_while(|&&byte| byte == 0).count();
+    let order_bytes = order.len().saturating_sub(zero_bytes);
+    let shift = order.get(zero_bytes).map_or(0, |byte| byte.leading_zeros()) as usize;
+
+    let mut field_bytes = FieldBytes::<C>::default();
+
+    if bits.len().saturating_mul(8) <= order_bytes.saturating_mul(8).saturating_sub(shift) {
+        for (dst, src) in field_bytes.iter_mut().rev().zip(bits.iter().rev()) {
+            *dst = *src;
+        }
+
+        return field_bytes;
+    }
+
+    for (dst, src) in field_bytes.iter_mut().skip(zero_bytes).zip(bits) {
+        *dst = *src;
+    }
+
+    if shift != 0 {
+        let mut carry = 0;
+
+        for byte in field_bytes.iter_mut().skip(zero_bytes) {
+            let next_carry = *byte << (8 - shift);
+            *byte = (*byte >> shift) | carry;
+            carry = next_carry;
+        }
+    }
+
+    field_bytes
+}
+
+/// Convert a message prehash to a scalar as described by `bits2int` in
+/// RFC 6979, reducing the result modulo the curve order.
+///
+/// Returns an error if the prehash is shorter than half the field size.
+#[cfg(any(feature = "sign", feature = "verify"))]
+pub(crate) fn prehash_to_scalar<C>(prehash: &[u8]) -> Result<Scalar<C>>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    if prehash.len() < FieldBytes::<C>::default().len() / 2 {
+        return Err(crate::Error::new());
+    }
+
+    Ok(reduce_field_bytes::<C>(&bits2field::<C>(prehash)))
+}
+
+/// Interpret big endian bytes as an integer and reduce it modulo the curve
+/// order.
+///
+/// The input is reduced by the scalar arithmetic rather than compared
+/// against the order, so any integer the size of a field element is
+/// accepted, e.g. [`bits2field`] output or an x-coordinate.
+#[cfg(feature = "arithmetic")]
+fn reduce_field_bytes<C>(bytes: &FieldBytes<C>) -> Scalar<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    let base = Scalar::<C>::from(0x100);
+
+    bytes.iter().fold(Scalar::<C>::zero(), |acc, &byte| {
+        acc * base + Scalar::<C>::from(u64::from(byte))
+    })
 }
 
 #[cfg(feature = "digest")]
@@ -159,3 +607,47 @@ where
 {
     type Digest = C::Digest;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::bits2field;
+    use elliptic_curve::{bigint::U192, dev::MockCurve, weierstrass};
+    use hex_literal::hex;
+
+    /// Curve whose order (163 bits) is not a multiple of 8 bits: only the
+    /// order of NIST K-163 is defined.
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+    struct K163;
+
+    impl elliptic_curve::Curve for K163 {
+        type UInt = U192;
+
+        const ORDER: U192 = U192::from_be_hex("00000004000000000000000000020108a2e0cc0d99f8a5ef");
+    }
+
+    impl weierstrass::Curve for K163 {}
+
+    #[test]
+    fn bits2field_truncates_long_input() {
+        let digest = [0x55; 64];
+        assert_eq!(bits2field::<MockCurve>(&digest).as_slice(), &digest[..32]);
+    }
+
+    #[test]
+    fn bits2field_pads_short_input() {
+        let digest = [0x55; 20];
+        let field_bytes = bits2field::<MockCurve>(&digest);
+        assert_eq!(&field_bytes[..12], &[0; 12]);
+        assert_eq!(&field_bytes[12..], &digest[..]);
+    }
+
+    /// SHA-256 of `sample` truncated to 163 bits as in RFC 6979 Appendix A.1.2
+    #[test]
+    fn bits2field_non_byte_aligned_order() {
+        let digest = hex!("af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf");
+        assert_eq!(
+            bits2field::<K163>(&digest).as_slice(),
+            &hex!("00000005795edf0d54db760f156f0eb4a7a0fe38d418e813")[..]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Derivation of secret scalars from seed material.
+//!
+//! Uses HKDF (RFC 5869) with an empty salt to expand the seed into a stream
+//! of candidate scalars, returning the first candidate which lies in the
+//! range `[1, n)`. Candidates are truncated to the bit length of the curve
+//! order before checking, as is done for RFC 6979 nonces.
+
+use crate::hazmat::bits2field;
+use elliptic_curve::{
+    generic_array::GenericArray, weierstrass::Curve, zeroize::Zeroize, FieldBytes, NonZeroScalar,
+    ProjectiveArithmetic, Scalar,
+};
+use hmac::{Hmac, Mac, NewMac};
+use signature::digest::{BlockInput, FixedOutput, Reset, Update};
+
+/// Derive a secret scalar from the given input keying material and
+/// context-specific `info` string.
+///
+/// Returns `None` if the HKDF output limit of 255 blocks is exhausted without
+/// finding a valid scalar.
+pub(crate) fn derive_scalar<C, D>(ikm: &[u8], info: &[u8]) -> Option<NonZeroScalar<C>>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    Scalar<C>: Zeroize,
+{
+    let mut okm = HkdfExpand::<D>::new(ikm, info);
+    let mut candidate = FieldBytes::<C>::default();
+    let mut result = None;
+
+    while result.is_none() && okm.fill(&mut candidate) {
+        result = NonZeroScalar::from_repr(bits2field::<C>(&candidate));
+    }
+
+    candidate.zeroize();
+    result
+}
+
+/// HKDF-Expand output stream.
+struct HkdfExpand<'a, D>
+where
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    /// HMAC keyed with the pseudorandom key `PRK`
+    prk: Hmac<D>,
+
+    /// Context-specific `info` string
+    info: &'a [u8],
+
+    /// Current output block `T(counter)`
+    t: GenericArray<u8, D::OutputSize>,
+
+    /// Number of bytes of `t` consumed so far
+    pos: usize,
+
+    /// Index of the current output block
+    counter: u8,
+}
+
+impl<'a, D> HkdfExpand<'a, D>
+where
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    /// Perform HKDF-Extract with an empty salt and prepare to expand.
+    fn new(ikm: &[u8], info: &'a [u8]) -> Self {
+        let mut extract = Hmac::<D>::new(&Default::default());
+        extract.update(ikm);
+        let mut prk = extract.finalize().into_bytes();
+        let hmac = Hmac::new_from_slice(&prk).expect("HMAC error");
+        prk.zeroize();
+
+        let t = GenericArray::default();
+        let pos = t.len();
+
+        Self {
+            prk: hmac,
+            info,
+            t,
+            pos,
+            counter: 0,
+        }
+    }
+
+    /// Fill `out` with the next bytes of output, returning `false` if the
+    /// maximum output length has been reached.
+    fn fill(&mut self, out: &mut [u8]) -> bool {
+        for byte in out {
+            if self.pos == self.t.len() {
+                if self.counter == u8::MAX {
+                    return false;
+                }
+
+                if self.counter > 0 {
+                    self.prk.update(&self.t);
+                }
+
+                self.counter += 1;
+                self.prk.update(self.info);
+                self.prk.update(&[self.counter]);
+                self.t = self.prk.finalize_reset().into_bytes();
+                self.pos = 0;
+            }
+
+            *byte = self.t[self.pos];
+            self.pos += 1;
+        }
+
+        true
+    }
+}
+
+impl<'a, D> Drop for HkdfExpand<'a, D>
+where
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    fn drop(&mut self) {
+        self.t.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{derive_scalar, HkdfExpand};
+    use elliptic_curve::{dev::MockCurve, group::ff::PrimeField};
+    use hex_literal::hex;
+    use sha2::Sha256;
+
+    /// RFC 5869 Appendix A.3 (SHA-256, zero-length salt and info)
+    #[test]
+    fn rfc5869_test_case_3() {
+        let ikm = [0x0b; 22];
+        let mut okm = [0u8; 42];
+        assert!(HkdfExpand::<Sha256>::new(&ikm, &[]).fill(&mut okm));
+        assert_eq!(
+            okm,
+            hex!(
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d"
+                "9d201395faa4b61a96c8"
+            )
+        );
+    }
+
+    #[test]
+    fn output_limit() {
+        let mut okm = [0u8; 255 * 32];
+        let mut hkdf = HkdfExpand::<Sha256>::new(b"seed", &[]);
+        assert!(hkdf.fill(&mut okm));
+        assert!(!hkdf.fill(&mut [0u8]));
+    }
+
+    #[test]
+    fn derive() {
+        let scalar = derive_scalar::<MockCurve, Sha256>(b"seed", b"info").unwrap();
+        assert_eq!(
+            scalar.to_repr().as_slice(),
+            &hex!("52f3dd54074f35f06f928d5b0b98b47845a6f9025ad632b5befaeab0bc452f66")[..]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Minimal parser for the flat JSON objects used by JOSE.
+
+use crate::{Error, Result};
+
+/// Parse a JSON object, calling `f` with the name and value of each member
+/// in order.
+///
+/// Only flat objects whose values are all strings without escape sequences
+/// are accepted. This covers JOSE headers and JWKs for ECDSA keys, while
+/// keeping the parser small enough to audit.
+pub(crate) fn for_each_member<'a>(
+    object: &'a str,
+    mut f: impl FnMut(&'a str, &'a str) -> Result<()>,
+) -> Result<()> {
+    let mut rest = object.trim().strip_prefix('{').ok_or_else(Error::new)?;
+
+    if rest.trim_start().strip_prefix('}').map(str::trim) == Some("") {
+        return Ok(());
+    }
+
+    loop {
+        let (name, after_name) = string(rest)?;
+        let after_colon = after_name
+            .trim_start()
+            .strip_prefix(':')
+            .ok_or_else(Error::new)?;
+        let (value, after_value) = string(after_colon)?;
+
+        f(name, value)?;
+
+        let after_value = after_value.trim_start();
+
+        if let Some(next) = after_value.strip_prefix(',') {
+            rest = next;
+        } else if after_value.strip_prefix('}').map(str::trim) == Some("") {
+            return Ok(());
+        } else {
+            return Err(Error::new());
+        }
+    }
+}
+
+/// Parse a JSON string without escape sequences from the start of `s`
+/// (after optional whitespace), returning it and the remaining input.
+fn string(s: &str) -> Result<(&str, &str)> {
+    let s = s.trim_start().strip_prefix('"').ok_or_else(Error::new)?;
+    let end = s.find('"').ok_or_else(Error::new)?;
+    let value = &s[..end];
+
+    if value.contains(|c: char| c == '\\' || c.is_control()) {
+        return Err(Error::new());
+    }
+
+    Ok((value, &s[(end + 1)..]))
+}
@@ -0,0 +1,269 @@
+//! Support for JSON Web Keys ([RFC 7517]) for elliptic curve keys, as
+//! described in [RFC 7518 Section 6.2].
+//!
+//! [RFC 7517]: https://tools.ietf.org/html/rfc7517
+//! [RFC 7518 Section 6.2]: https://tools.ietf.org/html/rfc7518#section-6.2
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    json, Error, Result, SignatureSize, SigningKey, VerifyingKey,
+};
+use alloc::string::String;
+use base64ct::{Base64UrlUnpadded, Encoding};
+use core::ops::Add;
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    ops::Invert,
+    sec1::{
+        EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize,
+    },
+    weierstrass::Curve,
+    zeroize::{Zeroize, Zeroizing},
+    AffinePoint, FieldBytes, ProjectiveArithmetic, Scalar,
+};
+
+/// JWK key type for elliptic curve keys
+const KTY_EC: &str = "EC";
+
+/// JWK elliptic curve identifier, as registered in the [IANA JOSE registry].
+///
+/// [IANA JOSE registry]: https://www.iana.org/assignments/jose/jose.xhtml
+pub trait JwkParameters: Curve {
+    /// JWK `crv` parameter value (e.g. `P-256`)
+    const CRV: &'static str;
+}
+
+impl<C> SigningKey<C>
+where
+    C: Curve + JwkParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`SigningKey`] from a JWK containing the `d` parameter.
+    ///
+    /// The `x` and `y` parameters are required, and must match the public
+    /// key computed from `d`.
+    pub fn from_jwk(jwk: &str) -> Result<Self> {
+        let jwk = EcJwk::parse::<C>(jwk)?;
+        let mut d = decode_field_bytes::<C>(jwk.d.ok_or_else(Error::new)?)?;
+        let signing_key = Self::from_bytes(&d);
+        d.zeroize();
+
+        let signing_key = signing_key?;
+        let public_key = signing_key.verifying_key().to_encoded_point(false);
+
+        if public_key.x() != Some(&decode_field_bytes::<C>(jwk.x)?)
+            || public_key.y() != Some(&decode_field_bytes::<C>(jwk.y)?)
+        {
+            return Err(Error::new());
+        }
+
+        Ok(signing_key)
+    }
+
+    /// Serialize this [`SigningKey`] as a JWK, including the public key.
+    pub fn to_jwk(&self) -> Zeroizing<String> {
+        let d = Zeroizing::new(Base64UrlUnpadded::encode_string(&self.to_zeroizing_bytes()));
+
+        // Replace the closing brace of the public key JWK with the `d` member
+        let mut jwk = Zeroizing::new(self.verifying_key().to_jwk());
+        jwk.pop();
+
+        for part in &[r#","d":""#, &d, r#""}"#] {
+            jwk.push_str(part);
+        }
+
+        jwk
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + JwkParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`VerifyingKey`] from a public key JWK.
+    ///
+    /// JWKs containing the `d` parameter are rejected, so that private keys
+    /// are not mistakenly handled as public ones.
+    pub fn from_jwk(jwk: &str) -> Result<Self> {
+        let jwk = EcJwk::parse::<C>(jwk)?;
+
+        if jwk.d.is_some() {
+            return Err(Error::new());
+        }
+
+        Self::from_encoded_point(&EncodedPoint::from_affine_coordinates(
+            &decode_field_bytes::<C>(jwk.x)?,
+            &decode_field_bytes::<C>(jwk.y)?,
+            false,
+        ))
+    }
+
+    /// Serialize this [`VerifyingKey`] as a public key JWK.
+    pub fn to_jwk(&self) -> String {
+        let public_key = self.to_encoded_point(false);
+        let mut jwk = String::new();
+
+        for part in &[
+            r#"{"kty":""#,
+            KTY_EC,
+            r#"","crv":""#,
+            C::CRV,
+            r#"","x":""#,
+            &Base64UrlUnpadded::encode_string(public_key.x().expect("uncompressed point")),
+            r#"","y":""#,
+            &Base64UrlUnpadded::encode_string(public_key.y().expect("uncompressed point")),
+            r#""}"#,
+        ] {
+            jwk.push_str(part);
+        }
+
+        jwk
+    }
+}
+
+/// Members of an elliptic curve JWK which have not yet been decoded.
+struct EcJwk<'a> {
+    /// Public key x-coordinate
+    x: &'a str,
+
+    /// Public key y-coordinate
+    y: &'a str,
+
+    /// Secret scalar, if this is a private key
+    d: Option<&'a str>,
+}
+
+impl<'a> EcJwk<'a> {
+    /// Parse a JWK, checking that it is an elliptic curve key for `C`.
+    ///
+    /// Members other than `kty`, `crv`, `x`, `y`, and `d` are ignored, but
+    /// must have string values (see [`json::for_each_member`]).
+    fn parse<C: JwkParameters>(jwk: &'a str) -> Result<Self> {
+        let (mut kty, mut crv, mut x, mut y, mut d) = (None, None, None, None, None);
+
+        json::for_each_member(jwk, |name, value| {
+            let member = match name {
+                "kty" => &mut kty,
+                "crv" => &mut crv,
+                "x" => &mut x,
+                "y" => &mut y,
+                "d" => &mut d,
+                _ => return Ok(()),
+            };
+
+            if member.replace(value).is_some() {
+                return Err(Error::new());
+            }
+
+            Ok(())
+        })?;
+
+        if kty != Some(KTY_EC) || crv != Some(C::CRV) {
+            return Err(Error::new());
+        }
+
+        Ok(Self {
+            x: x.ok_or_else(Error::new)?,
+            y: y.ok_or_else(Error::new)?,
+            d,
+        })
+    }
+}
+
+/// Decode a Base64url-encoded field element, which must be exactly the size
+/// of the field.
+fn decode_field_bytes<C: Curve>(encoded: &str) -> Result<FieldBytes<C>> {
+    let mut bytes = FieldBytes::<C>::default();
+    let len = Base64UrlUnpadded::decode(encoded, &mut bytes)
+        .map_err(|_| Error::new())?
+        .len();
+
+    if len != bytes.len() {
+        bytes.zeroize();
+        return Err(Error::new());
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{decode_field_bytes, EcJwk};
+    use crate::VerifyingKey;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    /// Private key from RFC 6979 Appendix 2.5 (NIST P-256) as a JWK
+    const JWK: &str = r#"{
+        "kty": "EC",
+        "crv": "P-256",
+        "x": "YP7UuiVanTHJYet0xjVtaMBJuJI7Yfps5mliLmDyn7Y",
+        "y": "eQP-EAi4vJmkGunpVii8ZPLxsgwtfp9Rd6PClNRGIpk",
+        "d": "ya-p2EW6dRZrXCFXZ7HWk05Qw9s26JsSe4piKxIPZyE",
+        "kid": "example"
+    }"#;
+
+    #[test]
+    fn parse_jwk() {
+        let jwk = EcJwk::parse::<MockCurve>(JWK).unwrap();
+
+        assert_eq!(
+            decode_field_bytes::<MockCurve>(jwk.x).unwrap().as_slice(),
+            &hex!("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6")[..]
+        );
+        assert_eq!(
+            decode_field_bytes::<MockCurve>(jwk.y).unwrap().as_slice(),
+            &hex!("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299")[..]
+        );
+        assert_eq!(
+            decode_field_bytes::<MockCurve>(jwk.d.unwrap())
+                .unwrap()
+                .as_slice(),
+            &hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")[..]
+        );
+    }
+
+    #[test]
+    fn verifying_key_jwk() {
+        let public_jwk = concat!(
+            r#"{"kty":"EC","crv":"P-256","#,
+            r#""x":"YP7UuiVanTHJYet0xjVtaMBJuJI7Yfps5mliLmDyn7Y","#,
+            r#""y":"eQP-EAi4vJmkGunpVii8ZPLxsgwtfp9Rd6PClNRGIpk"}"#
+        );
+
+        let verifying_key = VerifyingKey::<MockCurve>::from_jwk(public_jwk).unwrap();
+        assert_eq!(verifying_key.to_jwk(), public_jwk);
+
+        // Private keys are rejected
+        assert!(VerifyingKey::<MockCurve>::from_jwk(JWK).is_err());
+    }
+
+    #[test]
+    fn reject_invalid_jwks() {
+        for jwk in &[
+            r#"{"kty":"OKP","crv":"P-256","x":"AA","y":"AA"}"#,
+            r#"{"kty":"EC","crv":"P-384","x":"AA","y":"AA"}"#,
+            r#"{"kty":"EC","crv":"P-256","x":"AA"}"#,
+            r#"{"kty":"EC","crv":"P-256","x":"AA","y":"AA","x":"AA"}"#,
+            r#"{"kty":"EC","crv":"P-256","x":"AA","y":"AA","key_ops":["sign"]}"#,
+        ] {
+            assert!(EcJwk::parse::<MockCurve>(jwk).is_err());
+        }
+    }
+
+    #[test]
+    fn reject_wrong_length_field_bytes() {
+        // 31 bytes
+        assert!(
+            decode_field_bytes::<MockCurve>("YP7UuiVanTHJYet0xjVtaMBJuJI7Yfps5mliLmDynw").is_err()
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Minimal support for JSON Web Tokens ([RFC 7519]) signed using the JWS
+//! ECDSA algorithms (i.e. `ES256`, `ES384`, and `ES256K`).
+//!
+//! The JWS `alg` is derived from the curve of the key being used rather than
+//! read from the token, and tokens whose protected header names any other
+//! algorithm (including `none`) are rejected before the signature is checked.
+//! This rules out mismatched algorithm/curve pairs by construction.
+//!
+//! Claims are passed in and returned as serialized JSON: this module does
+//! not interpret them.
+//!
+//! [RFC 7519]: https://tools.ietf.org/html/rfc7519
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    json, Error, Result, Signature, SignatureSize, SigningKey, VerifyingKey,
+};
+use alloc::{format, string::String, vec::Vec};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use core::{convert::TryFrom, str};
+use elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, weierstrass::Curve, zeroize::Zeroize,
+    ProjectiveArithmetic, Scalar,
+};
+use signature::{Signer, Verifier};
+
+/// JWS algorithm identifier for an elliptic curve, as registered in the
+/// [IANA JOSE registry].
+///
+/// [IANA JOSE registry]: https://www.iana.org/assignments/jose/jose.xhtml
+pub trait JwsParameters: Curve {
+    /// JWS `alg` header parameter value (e.g. `ES256` for P-256)
+    const JWS_ALG: &'static str;
+}
+
+/// Sign the given serialized JSON claims set, returning a JWT in the JWS
+/// compact serialization.
+pub fn sign<C>(signing_key: &SigningKey<C>, claims: &str) -> Result<String>
+where
+    C: JwsParameters + ProjectiveArithmetic,
+    SigningKey<C>: Signer<Signature<C>>,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    let header = format!(r#"{{"alg":"{}","typ":"JWT"}}"#, C::JWS_ALG);
+    let mut token = format!(
+        "{}.{}",
+        Base64UrlUnpadded::encode_string(header.as_bytes()),
+        Base64UrlUnpadded::encode_string(claims.as_bytes())
+    );
+
+    let signature = signing_key.try_sign(token.as_bytes())?;
+    token.push('.');
+    token.push_str(&Base64UrlUnpadded::encode_string(signature.as_ref()));
+    Ok(token)
+}
+
+/// Verify a JWT in the JWS compact serialization, returning its serialized
+/// JSON claims set.
+pub fn verify<C>(verifying_key: &VerifyingKey<C>, token: &str) -> Result<String>
+where
+    C: JwsParameters + ProjectiveArithmetic,
+    VerifyingKey<C>: Verifier<Signature<C>>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    let token = Token::<C>::parse(token)?;
+    verifying_key.verify(token.signing_input.as_bytes(), &token.signature)?;
+    Ok(token.claims)
+}
+
+/// JWT whose header has been checked but whose signature has not.
+struct Token<'a, C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Encoded header and claims which the signature covers
+    signing_input: &'a str,
+
+    /// Decoded claims
+    claims: String,
+
+    /// Decoded signature
+    signature: Signature<C>,
+}
+
+impl<'a, C> Token<'a, C>
+where
+    C: JwsParameters,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Split a token into its parts, checking that its header names this
+    /// curve's algorithm.
+    fn parse(token: &'a str) -> Result<Self> {
+        let (signing_input, encoded_signature) = split_last_dot(token)?;
+        let (encoded_header, encoded_claims) = split_last_dot(signing_input)?;
+
+        let header = decode_utf8(encoded_header)?;
+        if header_alg(&header)? != C::JWS_ALG {
+            return Err(Error::new());
+        }
+
+        let claims = decode_utf8(encoded_claims)?;
+        let signature_bytes =
+            Base64UrlUnpadded::decode_vec(encoded_signature).map_err(|_| Error::new())?;
+        let signature = Signature::try_from(signature_bytes.as_slice())?;
+
+        Ok(Self {
+            signing_input,
+            claims,
+            signature,
+        })
+    }
+}
+
+/// Split `s` at its last `.`, rejecting empty trailing parts.
+fn split_last_dot(s: &str) -> Result<(&str, &str)> {
+    let pos = s.rfind('.').ok_or_else(Error::new)?;
+    let (head, tail) = (&s[..pos], &s[(pos + 1)..]);
+
+    if head.is_empty() || tail.is_empty() {
+        return Err(Error::new());
+    }
+
+    Ok((head, tail))
+}
+
+/// Decode unpadded Base64url into a UTF-8 string.
+fn decode_utf8(encoded: &str) -> Result<String> {
+    let bytes: Vec<u8> = Base64UrlUnpadded::decode_vec(encoded).map_err(|_| Error::new())?;
+    String::from_utf8(bytes).map_err(|_| Error::new())
+}
+
+/// Get the value of the `alg` parameter from a JOSE header.
+///
+/// Only headers which are flat JSON objects with string values not
+/// containing escape sequences are accepted, which covers the headers of
+/// all common JWTs. Headers with duplicate or missing `alg` parameters, or
+/// with a `crit` parameter (none of whose extensions are supported), are
+/// rejected.
+fn header_alg(header: &str) -> Result<&str> {
+    let mut alg = None;
+
+    json::for_each_member(header, |name, value| match name {
+        "alg" if alg.is_none() => {
+            alg = Some(value);
+            Ok(())
+        }
+        "alg" | "crit" => Err(Error::new()),
+        _ => Ok(()),
+    })?;
+
+    alg.ok_or_else(Error::new)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{header_alg, Token};
+    use alloc::string::String;
+    use base64ct::{Base64UrlUnpadded, Encoding};
+    use elliptic_curve::dev::MockCurve;
+
+    /// `{"alg":"ES256","typ":"JWT"}`
+    const HEADER: &str = "eyJhbGciOiJFUzI1NiIsInR5cCI6IkpXVCJ9";
+
+    /// `{"sub":"1234567890"}`
+    const CLAIMS: &str = "eyJzdWIiOiIxMjM0NTY3ODkwIn0";
+
+    fn token(header: &str) -> String {
+        let signature = Base64UrlUnpadded::encode_string(&[1u8; 64]);
+        [header, CLAIMS, &signature].join(".")
+    }
+
+    #[test]
+    fn parse_token() {
+        let token = token(HEADER);
+        let parsed = Token::<MockCurve>::parse(&token).unwrap();
+        assert_eq!(parsed.signing_input, [HEADER, CLAIMS].join("."));
+        assert_eq!(parsed.claims, r#"{"sub":"1234567890"}"#);
+    }
+
+    #[test]
+    fn reject_other_algs() {
+        for header in &[
+            r#"{"alg":"ES384"}"#,
+            r#"{"alg":"none"}"#,
+            r#"{"typ":"JWT"}"#,
+        ] {
+            let token = token(&Base64UrlUnpadded::encode_string(header.as_bytes()));
+            assert!(Token::<MockCurve>::parse(&token).is_err());
+        }
+    }
+
+    #[test]
+    fn reject_malformed_tokens() {
+        for token in &[
+            "",
+            ".",
+            "..",
+            HEADER,
+            &[HEADER, CLAIMS].join("."),
+            &[HEADER, CLAIMS, ""].join("."),
+        ] {
+            assert!(Token::<MockCurve>::parse(token).is_err());
+        }
+    }
+
+    #[test]
+    fn header_parsing() {
+        assert_eq!(
+            header_alg(r#" { "typ" : "JWT", "alg":"ES256" } "#).unwrap(),
+            "ES256"
+        );
+        assert_eq!(
+            header_alg(r#"{"alg":"ES256","kid":"key-1"}"#).unwrap(),
+            "ES256"
+        );
+        assert!(header_alg(r#"{"alg":"ES256","alg":"ES256"}"#).is_err());
+        assert!(header_alg(r#"{"alg":"ES256","crit":"exp"}"#).is_err());
+        assert!(header_alg(r#"{"alg":"ES256","b64":false}"#).is_err());
+        assert!(header_alg(r#"{"alg":"ES256"}x"#).is_err());
+        assert!(header_alg(r#"{"alg":"ES256""#).is_err());
+    }
+}
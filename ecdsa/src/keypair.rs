@@ -0,0 +1,129 @@
+//! ECDSA keypair.
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    Result, Signature, SignatureSize, SigningKey, VerifyingKey,
+};
+use elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, weierstrass::Curve, zeroize::Zeroize,
+    ProjectiveArithmetic, Scalar,
+};
+use signature::{
+    digest::Digest,
+    rand_core::{CryptoRng, RngCore},
+    DigestSigner, DigestVerifier, Signer, Verifier,
+};
+
+/// ECDSA keypair, i.e. a [`SigningKey`] along with its [`VerifyingKey`].
+///
+/// The verifying key is computed once when the keypair is constructed,
+/// rather than each time [`SigningKey::verifying_key`] is called.
+#[cfg_attr(docsrs, doc(cfg(all(feature = "sign", feature = "verify"))))]
+pub struct Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    signing_key: SigningKey<C>,
+    verifying_key: VerifyingKey<C>,
+}
+
+impl<C> Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Generate a cryptographically random [`Keypair`].
+    pub fn random(rng: impl CryptoRng + RngCore) -> Self {
+        SigningKey::random(rng).into()
+    }
+
+    /// Borrow the [`SigningKey`] for this keypair.
+    pub fn signing_key(&self) -> &SigningKey<C> {
+        &self.signing_key
+    }
+
+    /// Borrow the [`VerifyingKey`] for this keypair.
+    pub fn verifying_key(&self) -> &VerifyingKey<C> {
+        &self.verifying_key
+    }
+}
+
+impl<C> From<SigningKey<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn from(signing_key: SigningKey<C>) -> Keypair<C> {
+        let verifying_key = signing_key.verifying_key();
+
+        Keypair {
+            signing_key,
+            verifying_key,
+        }
+    }
+}
+
+impl<C> AsRef<VerifyingKey<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn as_ref(&self) -> &VerifyingKey<C> {
+        &self.verifying_key
+    }
+}
+
+impl<C, D> DigestSigner<D, Signature<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    D: Digest,
+    SignatureSize<C>: ArrayLength<u8>,
+    SigningKey<C>: DigestSigner<D, Signature<C>>,
+{
+    fn try_sign_digest(&self, digest: D) -> Result<Signature<C>> {
+        self.signing_key.try_sign_digest(digest)
+    }
+}
+
+impl<C> Signer<Signature<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+    SigningKey<C>: Signer<Signature<C>>,
+{
+    fn try_sign(&self, msg: &[u8]) -> Result<Signature<C>> {
+        self.signing_key.try_sign(msg)
+    }
+}
+
+impl<C, D> DigestVerifier<D, Signature<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    D: Digest,
+    SignatureSize<C>: ArrayLength<u8>,
+    VerifyingKey<C>: DigestVerifier<D, Signature<C>>,
+{
+    fn verify_digest(&self, digest: D, signature: &Signature<C>) -> Result<()> {
+        self.verifying_key.verify_digest(digest, signature)
+    }
+}
+
+impl<C> Verifier<Signature<C>> for Keypair<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+    VerifyingKey<C>: Verifier<Signature<C>>,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
+        self.verifying_key.verify(msg, signature)
+    }
+}
@@ -0,0 +1,180 @@
+//! Key handle registry for applications holding many signing keys.
+//!
+//! A [`KeyStore`] owns signing keys and hands out opaque [`KeyHandle`]s in
+//! exchange. Callers can sign messages using a handle, subject to the
+//! [`UsagePolicy`] attached to it, without the key itself ever crossing
+//! module boundaries.
+//!
+//! [`MemoryKeyStore`] is an in-memory reference implementation.
+
+use crate::{Error, Result};
+use alloc::{boxed::Box, collections::BTreeMap};
+use signature::{Signature, Signer};
+
+/// Opaque handle to a key held by a [`KeyStore`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct KeyHandle(u64);
+
+/// Restrictions on how a key held by a [`KeyStore`] may be used.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct UsagePolicy {
+    /// Maximum number of signatures which can be produced with the key.
+    pub max_signatures: Option<u64>,
+
+    /// Maximum length of messages which can be signed with the key.
+    pub max_message_len: Option<usize>,
+}
+
+impl UsagePolicy {
+    /// Check whether a message of the given length may be signed after
+    /// `signatures` signatures have already been produced.
+    pub fn permits(&self, signatures: u64, message_len: usize) -> bool {
+        !matches!(self.max_signatures, Some(max) if signatures >= max)
+            && !matches!(self.max_message_len, Some(max) if message_len > max)
+    }
+}
+
+/// Registry mapping opaque [`KeyHandle`]s to signers.
+pub trait KeyStore<S: Signature> {
+    /// Add a signer to this key store, returning its handle.
+    fn insert(
+        &mut self,
+        signer: Box<dyn Signer<S> + Send + Sync>,
+        policy: UsagePolicy,
+    ) -> KeyHandle;
+
+    /// Sign the given message using the key with the given handle.
+    ///
+    /// Returns an error if the handle is unknown or its [`UsagePolicy`] does
+    /// not permit signing the message.
+    fn try_sign(&mut self, handle: KeyHandle, msg: &[u8]) -> Result<S>;
+
+    /// Remove the key with the given handle, returning `true` if it existed.
+    fn remove(&mut self, handle: KeyHandle) -> bool;
+}
+
+/// Key held by a [`MemoryKeyStore`].
+struct Entry<S> {
+    /// Signer
+    signer: Box<dyn Signer<S> + Send + Sync>,
+
+    /// Usage policy
+    policy: UsagePolicy,
+
+    /// Number of signatures produced so far
+    signatures: u64,
+}
+
+/// In-memory [`KeyStore`].
+pub struct MemoryKeyStore<S> {
+    /// Keys indexed by handle
+    entries: BTreeMap<KeyHandle, Entry<S>>,
+
+    /// Next handle to allocate
+    next_handle: u64,
+}
+
+impl<S> MemoryKeyStore<S> {
+    /// Create a new, empty key store.
+    pub fn new() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            next_handle: 0,
+        }
+    }
+
+    /// Get the number of keys in this key store.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is this key store empty?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<S> Default for MemoryKeyStore<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Signature> KeyStore<S> for MemoryKeyStore<S> {
+    fn insert(
+        &mut self,
+        signer: Box<dyn Signer<S> + Send + Sync>,
+        policy: UsagePolicy,
+    ) -> KeyHandle {
+        let handle = KeyHandle(self.next_handle);
+        self.next_handle = self.next_handle.checked_add(1).expect("handle overflow");
+
+        self.entries.insert(
+            handle,
+            Entry {
+                signer,
+                policy,
+                signatures: 0,
+            },
+        );
+
+        handle
+    }
+
+    fn try_sign(&mut self, handle: KeyHandle, msg: &[u8]) -> Result<S> {
+        let entry = self.entries.get_mut(&handle).ok_or_else(Error::new)?;
+
+        if !entry.policy.permits(entry.signatures, msg.len()) {
+            return Err(Error::new());
+        }
+
+        let signature = entry.signer.try_sign(msg)?;
+        entry.signatures += 1;
+        Ok(signature)
+    }
+
+    fn remove(&mut self, handle: KeyHandle) -> bool {
+        self.entries.remove(&handle).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyStore, MemoryKeyStore, UsagePolicy};
+    use alloc::boxed::Box;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+    use signature::Signer;
+
+    type Signature = crate::Signature<MockCurve>;
+
+    /// Signer which always returns the same signature
+    struct FixedSigner;
+
+    impl Signer<Signature> for FixedSigner {
+        fn try_sign(&self, _msg: &[u8]) -> signature::Result<Signature> {
+            Signature::from_scalars(
+                hex!("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+                hex!("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+            )
+        }
+    }
+
+    #[test]
+    fn enforces_usage_policy() {
+        let mut key_store = MemoryKeyStore::<Signature>::new();
+        let policy = UsagePolicy {
+            max_signatures: Some(1),
+            max_message_len: Some(6),
+        };
+        let handle = key_store.insert(Box::new(FixedSigner), policy);
+
+        assert!(key_store.try_sign(handle, b"sample too long").is_err());
+        assert!(key_store.try_sign(handle, b"sample").is_ok());
+        assert!(key_store.try_sign(handle, b"sample").is_err());
+
+        assert!(key_store.remove(handle));
+        assert!(key_store.is_empty());
+        assert!(key_store.try_sign(handle, b"sample").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! Verification of signatures which use deprecated digest algorithms.
+//!
+//! # ⚠️ Security Warning
+//!
+//! SHA-1 is not collision resistant. These functions exist so that archival
+//! tooling can validate signatures on historical artifacts, and must not be
+//! used to accept new signatures. No corresponding signing functionality is
+//! provided.
+
+use crate::{
+    hazmat::{bits2field, VerifyPrimitive},
+    Error, Result, Signature, SignatureSize, VerifyingKey,
+};
+use elliptic_curve::{
+    generic_array::ArrayLength, group::ff::PrimeField, weierstrass::Curve, AffinePoint,
+    ProjectiveArithmetic, Scalar,
+};
+
+/// Size of a SHA-1 digest in bytes
+pub const SHA1_DIGEST_SIZE: usize = 20;
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: VerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Verify the signature against a SHA-1 digest of the message, computed
+    /// by the caller using an implementation of their choice.
+    ///
+    /// See the [module-level documentation](self) before using this.
+    pub fn verify_legacy_sha1_prehash(
+        &self,
+        sha1_digest: &[u8; SHA1_DIGEST_SIZE],
+        signature: &Signature<C>,
+    ) -> Result<()> {
+        let msg_scalar = sha1_prehash_to_scalar::<C>(sha1_digest)?;
+        self.inner
+            .as_affine()
+            .verify_prehashed(&msg_scalar, signature)
+    }
+}
+
+/// Convert a SHA-1 digest to a scalar as described by `bits2int` in RFC 6979.
+///
+/// The digest is shorter than the order of every supported curve, so it is
+/// zero-extended and never reduced.
+fn sha1_prehash_to_scalar<C>(sha1_digest: &[u8; SHA1_DIGEST_SIZE]) -> Result<Scalar<C>>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    Scalar::<C>::from_repr(bits2field::<C>(sha1_digest)).ok_or_else(Error::new)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::sha1_prehash_to_scalar;
+    use elliptic_curve::{dev::MockCurve, group::ff::PrimeField};
+    use hex_literal::hex;
+
+    #[test]
+    fn sha1_prehash_is_zero_extended() {
+        // SHA-1("sample")
+        let digest = hex!("8151325dcdbae9e0ff95f9f9658432dbedfdb209");
+        let scalar = sha1_prehash_to_scalar::<MockCurve>(&digest).unwrap();
+
+        assert_eq!(
+            scalar.to_repr().as_slice(),
+            &hex!("0000000000000000000000008151325dcdbae9e0ff95f9f9658432dbedfdb209")[..]
+        );
+    }
+}
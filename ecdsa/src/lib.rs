@@ -55,6 +55,28 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(feature = "tracing", feature = "verify"))]
+extern crate tracing_crate as tracing;
+
+#[cfg(feature = "attestation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "attestation")))]
+pub mod attestation;
+
+#[cfg(all(feature = "sign", feature = "std"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "sign", feature = "std"))))]
+pub mod batch;
+
+#[cfg(feature = "cose")]
+#[cfg_attr(docsrs, doc(cfg(feature = "cose")))]
+pub mod cose;
+
+#[cfg(feature = "ctap2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ctap2")))]
+pub mod ctap2;
+
 #[cfg(feature = "der")]
 #[cfg_attr(docsrs, doc(cfg(feature = "der")))]
 pub mod der;
@@ -63,33 +85,122 @@ pub mod der;
 #[cfg_attr(docsrs, doc(cfg(feature = "dev")))]
 pub mod dev;
 
+#[cfg(feature = "did")]
+#[cfg_attr(docsrs, doc(cfg(feature = "did")))]
+pub mod did;
+
+#[cfg(feature = "dns")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dns")))]
+pub mod dns;
+
+#[cfg(feature = "fingerprint")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fingerprint")))]
+pub mod fingerprint;
+
 #[cfg(feature = "hazmat")]
 #[cfg_attr(docsrs, doc(cfg(feature = "hazmat")))]
 pub mod hazmat;
 
+#[cfg(feature = "sign")]
+mod hkdf;
+
+#[cfg(feature = "jwk")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwk")))]
+pub mod jwk;
+
+#[cfg(feature = "jose")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jose")))]
+pub mod jwt;
+
+#[cfg(any(feature = "jose", feature = "jwk"))]
+mod json;
+
+#[cfg(all(feature = "sign", feature = "verify"))]
+mod keypair;
+
+#[cfg(feature = "keystore")]
+#[cfg_attr(docsrs, doc(cfg(feature = "keystore")))]
+pub mod keystore;
+
+#[cfg(feature = "legacy-digests")]
+#[cfg_attr(docsrs, doc(cfg(feature = "legacy-digests")))]
+pub mod legacy;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod migrate;
+
+#[cfg(feature = "multibase")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multibase")))]
+pub mod multibase;
+
+#[cfg(feature = "sign")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
+pub mod nonce;
+
+#[cfg(feature = "pgp")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pgp")))]
+pub mod pgp;
+
+pub mod prelude;
+
+mod recovery;
+
 #[cfg(feature = "sign")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
 pub mod rfc6979;
 
+#[cfg(feature = "sec1")]
+mod sec1;
+
+#[cfg(feature = "sign")]
+mod session;
+
 #[cfg(feature = "sign")]
 mod sign;
 
+#[cfg(feature = "ssh")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ssh")))]
+pub mod ssh;
+
+#[cfg(feature = "verify")]
+mod validation;
+
 #[cfg(feature = "verify")]
 mod verify;
 
+#[cfg(feature = "x509")]
+#[cfg_attr(docsrs, doc(cfg(feature = "x509")))]
+pub mod x509;
+
 // Re-export the `elliptic-curve` crate (and select types)
 pub use elliptic_curve::{self, sec1::EncodedPoint, weierstrass::Curve};
 
 // Re-export the `signature` crate (and select types)
 pub use signature::{self, Error, Result};
 
+pub use recovery::RecoveryId;
+
+#[cfg(all(feature = "sign", feature = "verify"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "sign", feature = "verify"))))]
+pub use keypair::Keypair;
+
 #[cfg(feature = "sign")]
 #[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
-pub use sign::SigningKey;
+pub use {
+    session::{PreparedSigningKey, SigningSession},
+    sign::{SecretBytes, SigningKey},
+};
 
 #[cfg(feature = "verify")]
 #[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
-pub use verify::VerifyingKey;
+pub use {
+    validation::{PointValidationError, PublicKeyValidation, ValidationReport},
+    verify::{
+        CanonicalVerifyingKey, CompactVerifyingKey, PrecomputedVerifyingKey, VerifyContext,
+        VerifyingKey,
+    },
+};
 
 use core::{
     convert::TryFrom,
@@ -111,6 +222,19 @@ pub type SignatureSize<C> = <FieldSize<C> as Add>::Output;
 /// Fixed-size byte array containing an ECDSA signature
 pub type SignatureBytes<C> = GenericArray<u8, SignatureSize<C>>;
 
+/// ASN.1 DER-encoded ECDSA signature.
+///
+/// Alias for [`der::Signature`], for use in APIs which want to make the
+/// expected wire format explicit.
+#[cfg(feature = "der")]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+pub type DerSignature<C> = der::Signature<C>;
+
+/// Borrowed ASN.1 DER-encoded ECDSA signature (see [`der::SignatureRef`]).
+#[cfg(feature = "der")]
+#[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+pub type DerSignatureRef<'a, C> = der::SignatureRef<'a, C>;
+
 /// ECDSA signature (fixed-size). Generic over elliptic curve types.
 ///
 /// Serialized as fixed-sized big endian scalar values with no added framing:
@@ -143,6 +267,70 @@ where
         Self::try_from(r.into().concat(s.into()).as_slice())
     }
 
+    /// Parse a fixed-size signature whose `r` and `s` components are each
+    /// serialized big endian, i.e. the same encoding as [`TryFrom`].
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+
+    /// Parse a fixed-size signature whose `r` and `s` components (in that
+    /// order) are each serialized little endian, as exported by some HSMs
+    /// and smartcards.
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != <SignatureSize<C>>::to_usize() {
+            return Err(Error::new());
+        }
+
+        let (r, s) = bytes.split_at(C::UInt::BYTE_SIZE);
+        Self::from_scalars(reverse_field_bytes::<C>(r)?, reverse_field_bytes::<C>(s)?)
+    }
+
+    /// Check that the given bytes are a valid fixed-size signature for this
+    /// curve, i.e. that both `r` and `s` are in the range `[1, n-1]`.
+    ///
+    /// Unlike [`TryFrom`], this returns the specific reason the signature was
+    /// rejected. With the `std` feature enabled, the same reason is also
+    /// available as the [`std::error::Error::source`] of the [`Error`]
+    /// returned when parsing fails.
+    #[deny(clippy::indexing_slicing, clippy::panic)]
+    pub fn validate_bytes(bytes: &[u8]) -> core::result::Result<(), SignatureRangeError> {
+        if bytes.len() != <SignatureSize<C>>::to_usize() {
+            return Err(SignatureRangeError::InvalidLength);
+        }
+
+        let (r, s) = bytes.split_at(C::UInt::BYTE_SIZE);
+        let r = FieldBytes::<C>::from_exact_iter(r.iter().copied())
+            .ok_or(SignatureRangeError::InvalidLength)?;
+        let s = FieldBytes::<C>::from_exact_iter(s.iter().copied())
+            .ok_or(SignatureRangeError::InvalidLength)?;
+
+        if r.iter().all(|&byte| byte == 0) {
+            return Err(SignatureRangeError::ZeroR);
+        }
+
+        if s.iter().all(|&byte| byte == 0) {
+            return Err(SignatureRangeError::ZeroS);
+        }
+
+        if ScalarBytes::<C>::new(r).is_none().into() {
+            return Err(SignatureRangeError::ROutOfRange);
+        }
+
+        if ScalarBytes::<C>::new(s).is_none().into() {
+            return Err(SignatureRangeError::SOutOfRange);
+        }
+
+        Ok(())
+    }
+
+    /// Write the fixed-size encoding of this signature into the start of
+    /// `buf`, returning the number of bytes written.
+    ///
+    /// Returns an error if `buf` is too short.
+    pub fn write_bytes(&self, buf: &mut [u8]) -> Result<usize> {
+        write_into(self.as_ref(), buf)
+    }
+
     /// Parse a signature from ASN.1 DER
     #[cfg(feature = "der")]
     #[cfg_attr(docsrs, doc(cfg(feature = "der")))]
@@ -165,6 +353,20 @@ where
         let (r, s) = self.bytes.split_at(C::UInt::BYTE_SIZE);
         der::Signature::from_scalar_bytes(r, s).expect("DER encoding error")
     }
+
+    /// Write the ASN.1 DER encoding of this signature into the start of
+    /// `buf`, returning the number of bytes written.
+    ///
+    /// Returns an error if `buf` is too short.
+    #[cfg(feature = "der")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+    pub fn write_der(&self, buf: &mut [u8]) -> Result<usize>
+    where
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+    {
+        write_into(self.to_der().as_bytes(), buf)
+    }
 }
 
 #[cfg(feature = "arithmetic")]
@@ -185,7 +387,12 @@ where
     pub fn s(&self) -> NonZeroScalar<C> {
         let s_bytes = GenericArray::clone_from_slice(&self.bytes[C::UInt::BYTE_SIZE..]);
         NonZeroScalar::from_repr(s_bytes)
-            .unwrap_or_else(|| unreachable!("r-component ensured valid in constructor"))
+            .unwrap_or_else(|| unreachable!("s-component ensured valid in constructor"))
+    }
+
+    /// Split the signature into its `r` and `s` components
+    pub fn split_scalars(&self) -> (NonZeroScalar<C>, NonZeroScalar<C>) {
+        (self.r(), self.s())
     }
 
     /// Normalize signature into "low S" form as described in
@@ -261,30 +468,179 @@ where
 {
     type Error = Error;
 
+    #[deny(clippy::indexing_slicing, clippy::panic)]
     fn try_from(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() != <SignatureSize<C>>::to_usize() {
-            return Err(Error::new());
+        Self::validate_bytes(bytes)?;
+
+        GenericArray::from_exact_iter(bytes.iter().copied())
+            .map(|bytes| Self { bytes })
+            .ok_or_else(Error::new)
+    }
+}
+
+/// ECDSA signature in the fixed-size encoding described in IEEE P1363.
+///
+/// Wraps a [`Signature`], which uses the same encoding, so APIs can state the
+/// wire format they expect in their types. Converts to and from
+/// [`Signature`] using [`From`], and with the `der` feature, to and from
+/// [`DerSignature`] using [`From`] and [`TryFrom`].
+#[derive(Clone, Eq, PartialEq)]
+pub struct FixedSignature<C: Curve>(Signature<C>)
+where
+    SignatureSize<C>: ArrayLength<u8>;
+
+impl<C> signature::Signature for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+}
+
+impl<C> AsRef<[u8]> for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<C> Copy for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+    <SignatureSize<C> as ArrayLength<u8>>::ArrayType: Copy,
+{
+}
+
+impl<C> Debug for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FixedSignature").field(&self.0).finish()
+    }
+}
+
+impl<C> From<Signature<C>> for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn from(signature: Signature<C>) -> FixedSignature<C> {
+        FixedSignature(signature)
+    }
+}
+
+impl<C> From<FixedSignature<C>> for Signature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn from(signature: FixedSignature<C>) -> Signature<C> {
+        signature.0
+    }
+}
+
+impl<C> TryFrom<&[u8]> for FixedSignature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Signature::try_from(bytes).map(FixedSignature)
+    }
+}
+
+/// Reasons a serialized [`Signature`] can be rejected when parsed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SignatureRangeError {
+    /// Input is not the size of a signature for the given curve
+    InvalidLength,
+
+    /// The `r` component is zero
+    ZeroR,
+
+    /// The `s` component is zero
+    ZeroS,
+
+    /// The `r` component is not less than the curve's order
+    ROutOfRange,
+
+    /// The `s` component is not less than the curve's order
+    SOutOfRange,
+}
+
+impl fmt::Display for SignatureRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidLength => "invalid signature length",
+            Self::ZeroR => "signature r-component is zero",
+            Self::ZeroS => "signature s-component is zero",
+            Self::ROutOfRange => "signature r-component is out of range",
+            Self::SOutOfRange => "signature s-component is out of range",
+        })
+    }
+}
+
+impl From<SignatureRangeError> for Error {
+    fn from(err: SignatureRangeError) -> Error {
+        #[cfg(feature = "std")]
+        {
+            Error::from_source(err)
         }
 
-        for scalar in bytes.chunks_exact(C::UInt::BYTE_SIZE) {
-            if scalar.iter().all(|&byte| byte == 0) {
-                return Err(Error::new());
-            }
-
-            if ScalarBytes::<C>::new(GenericArray::clone_from_slice(scalar))
-                .is_none()
-                .into()
-            {
-                return Err(Error::new());
-            }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = err;
+            Error::new()
         }
+    }
+}
 
-        Ok(Self {
-            bytes: GenericArray::clone_from_slice(bytes),
-        })
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureRangeError {}
+
+/// Random number generator failure, e.g. a hardware RNG reporting a health
+/// test failure.
+///
+/// When randomized signing fails for this reason, this is the
+/// [`std::error::Error::source`] of the returned [`Error`] (with the `std`
+/// feature enabled).
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct RngError;
+
+impl fmt::Display for RngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("random number generator failure")
+    }
+}
+
+impl From<RngError> for Error {
+    fn from(err: RngError) -> Error {
+        #[cfg(feature = "std")]
+        {
+            Error::from_source(err)
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = err;
+            Error::new()
+        }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for RngError {}
+
 /// Normalize a scalar (i.e. ECDSA S) to the lower half the field, as described
 /// in [BIP 0062: Dealing with Malleability][1].
 ///
@@ -298,3 +654,19 @@ pub trait NormalizeLow: Sized {
     /// May be implemented to work in variable time.
     fn normalize_low(&self) -> (Self, bool);
 }
+
+/// Copy `bytes` into the start of `buf`, returning the number of bytes
+/// written, or an error if `buf` is too short.
+pub(crate) fn write_into(bytes: &[u8], buf: &mut [u8]) -> Result<usize> {
+    buf.get_mut(..bytes.len())
+        .ok_or_else(Error::new)?
+        .copy_from_slice(bytes);
+
+    Ok(bytes.len())
+}
+
+/// Convert a little endian field element into big endian [`FieldBytes`],
+/// returning an error if `bytes` is not exactly the size of the field.
+pub(crate) fn reverse_field_bytes<C: Curve>(bytes: &[u8]) -> Result<FieldBytes<C>> {
+    FieldBytes::<C>::from_exact_iter(bytes.iter().rev().copied()).ok_or_else(Error::new)
+}
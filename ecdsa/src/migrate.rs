@@ -0,0 +1,137 @@
+//! Re-signing stored signatures with a new key, e.g. when migrating from
+//! ECDSA to a post-quantum signature algorithm.
+//!
+//! [`resign`] is generic over the [`signature`] traits, so the old and new
+//! keys may use any algorithms which implement them.
+
+use alloc::vec::Vec;
+use signature::{Signature, Signer, Verifier};
+
+/// Entry to be migrated: a payload, its existing signature, and the key
+/// which that signature is checked against.
+pub type Entry<'a, S, V> = (&'a [u8], &'a S, &'a V);
+
+/// Reasons an entry could not be migrated.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MigrationError {
+    /// The existing signature did not verify, so the payload was not
+    /// re-signed
+    InvalidSignature,
+
+    /// Signing with the new key failed
+    SigningFailed,
+}
+
+/// Outcome of [`resign`], in which entries are identified by their position
+/// in the input.
+#[derive(Clone, Debug)]
+pub struct MigrationReport<T> {
+    /// Re-signed entries and their new signatures
+    pub migrated: Vec<(usize, T)>,
+
+    /// Entries which were not migrated, and why
+    pub failed: Vec<(usize, MigrationError)>,
+}
+
+impl<T> MigrationReport<T> {
+    /// Were all entries migrated?
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Verify the existing signature of each entry, and re-sign the payloads of
+/// those which verify using `new_signer`.
+///
+/// Every entry is processed even if some fail; the failures are listed in
+/// the returned report rather than aborting the migration.
+pub fn resign<'a, S, V, T>(
+    entries: impl IntoIterator<Item = Entry<'a, S, V>>,
+    new_signer: &impl Signer<T>,
+) -> MigrationReport<T>
+where
+    S: Signature + 'a,
+    V: Verifier<S> + 'a,
+    T: Signature,
+{
+    let mut report = MigrationReport {
+        migrated: Vec::new(),
+        failed: Vec::new(),
+    };
+
+    for (index, (payload, signature, verifier)) in entries.into_iter().enumerate() {
+        if verifier.verify(payload, signature).is_err() {
+            report
+                .failed
+                .push((index, MigrationError::InvalidSignature));
+            continue;
+        }
+
+        match new_signer.try_sign(payload) {
+            Ok(new_signature) => report.migrated.push((index, new_signature)),
+            Err(_) => report.failed.push((index, MigrationError::SigningFailed)),
+        }
+    }
+
+    report
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{resign, MigrationError};
+    use crate::dev::MockRemoteSigner;
+    use core::convert::TryFrom;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+    use signature::{Signer, Verifier};
+
+    type Signature = crate::Signature<MockCurve>;
+
+    /// Signature from RFC 6979 Appendix 2.5 (NIST P-256 + SHA-256, "sample")
+    const EXAMPLE_SIGNATURE: [u8; 64] = hex!(
+        "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+        "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+    );
+
+    /// Key which accepts only the example signature over `sample`
+    struct ExampleKey;
+
+    impl Verifier<Signature> for ExampleKey {
+        fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), crate::Error> {
+            if msg == b"sample" && signature.as_ref() == &EXAMPLE_SIGNATURE[..] {
+                Ok(())
+            } else {
+                Err(crate::Error::new())
+            }
+        }
+    }
+
+    impl Signer<Signature> for ExampleKey {
+        fn try_sign(&self, _msg: &[u8]) -> Result<Signature, crate::Error> {
+            Signature::try_from(&EXAMPLE_SIGNATURE[..])
+        }
+    }
+
+    #[test]
+    fn report_lists_each_entry() {
+        let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+        let entries = [
+            (&b"sample"[..], &signature, &ExampleKey),
+            (&b"tampered"[..], &signature, &ExampleKey),
+            (&b"sample"[..], &signature, &ExampleKey),
+        ];
+
+        let new_signer = MockRemoteSigner::new(ExampleKey).fail_next(1);
+        let report = resign(entries.iter().copied(), &new_signer);
+
+        assert!(!report.is_complete());
+        assert_eq!(report.migrated, [(2, signature)]);
+        assert_eq!(
+            report.failed,
+            [
+                (0, MigrationError::SigningFailed),
+                (1, MigrationError::InvalidSignature)
+            ]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Text encodings of ECDSA public keys: SEC1 hex, and [multibase]-encoded
+//! [multicodec] keys as used by e.g. `did:key` identifiers.
+//!
+//! [multibase]: https://github.com/multiformats/multibase
+//! [multicodec]: https://github.com/multiformats/multicodec
+
+use crate::{Error, Result, VerifyingKey};
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Write, ops::Add};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::{typenum::Unsigned, ArrayLength},
+    sec1::{FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+    weierstrass::Curve,
+    AffinePoint, ProjectiveArithmetic,
+};
+
+/// Multibase prefix for base58btc (Bitcoin alphabet) encoding.
+const BASE58BTC_PREFIX: char = 'z';
+
+/// Maximum length of a multiformats varint.
+const MAX_VARINT_LEN: usize = 9;
+
+/// Base58 alphabet used by Bitcoin and multibase.
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Identify an elliptic curve's public keys using a multicodec code.
+pub trait MulticodecParameters: Curve {
+    /// Multicodec code for compressed SEC1 public keys on this curve, e.g.
+    /// `0x1200` (`p256-pub`) for NIST P-256 or `0xe7` (`secp256k1-pub`).
+    const PUBLIC_KEY_CODE: u64;
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Serialize this [`VerifyingKey`] as lower case hex of its SEC1 encoding,
+    /// optionally applying point compression.
+    pub fn to_sec1_hex(&self, compress: bool) -> String {
+        let encoded_point = self.to_encoded_point(compress);
+        let mut hex = String::with_capacity(encoded_point.len() * 2);
+
+        for byte in encoded_point.as_bytes() {
+            write!(hex, "{:02x}", byte).expect("write to String failed");
+        }
+
+        hex
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + MulticodecParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Serialize this [`VerifyingKey`] as a multibase (base58btc) string of
+    /// its multicodec-prefixed compressed SEC1 encoding, e.g. `zDn...` for a
+    /// NIST P-256 key.
+    pub fn to_multibase(&self) -> String {
+        let mut bytes = Vec::new();
+        encode_varint(C::PUBLIC_KEY_CODE, &mut bytes);
+        bytes.extend_from_slice(self.to_encoded_point(true).as_bytes());
+
+        let mut multibase = String::new();
+        multibase.push(BASE58BTC_PREFIX);
+        encode_base58(&bytes, &mut multibase);
+        multibase
+    }
+
+    /// Parse a [`VerifyingKey`] from a multibase (base58btc) string of its
+    /// multicodec-prefixed compressed SEC1 encoding.
+    ///
+    /// Returns an error if the multicodec code doesn't match this curve's
+    /// [`MulticodecParameters::PUBLIC_KEY_CODE`].
+    pub fn from_multibase(multibase: &str) -> Result<Self> {
+        let encoded = multibase
+            .strip_prefix(BASE58BTC_PREFIX)
+            .ok_or_else(Error::new)?;
+
+        // Decoding is quadratic in the length of the input, so reject input
+        // which is too long to be any encoded key before decoding it
+        let max_len = max_base58_len(MAX_VARINT_LEN + UncompressedPointSize::<C>::USIZE);
+
+        if encoded.len() > max_len {
+            return Err(Error::new());
+        }
+
+        let bytes = decode_base58(encoded)?;
+        let (code, key_bytes) = decode_varint(&bytes)?;
+
+        if code != C::PUBLIC_KEY_CODE || !matches!(key_bytes.first(), Some(0x02) | Some(0x03)) {
+            return Err(Error::new());
+        }
+
+        Self::from_sec1_bytes(key_bytes)
+    }
+}
+
+/// Encode an unsigned varint as used by multicodec (i.e. unsigned LEB128)
+fn encode_varint(mut value: u64, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+
+    out.push(value as u8);
+}
+
+/// Decode a minimally-encoded unsigned varint, returning the remaining input
+fn decode_varint(bytes: &[u8]) -> Result<(u64, &[u8])> {
+    let mut value = 0u64;
+
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+
+        if byte & 0x80 == 0 {
+            // Reject non-minimal encodings, i.e. trailing zero groups
+            if byte == 0 && i > 0 {
+                return Err(Error::new());
+            }
+
+            return Ok((value, &bytes[(i + 1)..]));
+        }
+    }
+
+    Err(Error::new())
+}
+
+/// Get an upper bound on the length of the base58 encoding of `len` bytes,
+/// using log(256) / log(58) < 1.38
+fn max_base58_len(len: usize) -> usize {
+    len * 138 / 100 + 1
+}
+
+/// Encode bytes as base58 using the Bitcoin alphabet
+fn encode_base58(bytes: &[u8], out: &mut String) {
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+
+    // Base-58 digits of the input, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(max_base58_len(bytes.len()));
+
+    for &byte in &bytes[zeros..] {
+        let mut carry = u32::from(byte);
+
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    for _ in 0..zeros {
+        out.push('1');
+    }
+
+    out.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| BASE58_ALPHABET[digit as usize] as char),
+    );
+}
+
+/// Decode base58 using the Bitcoin alphabet
+fn decode_base58(encoded: &str) -> Result<Vec<u8>> {
+    let zeros = encoded.bytes().take_while(|&c| c == b'1').count();
+
+    // Bytes of the output, least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len() * 733 / 1000 + 1);
+
+    for c in encoded.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(Error::new)? as u32;
+
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    bytes.resize(bytes.len() + zeros, 0);
+    bytes.reverse();
+    Ok(bytes)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{decode_base58, decode_varint, encode_base58, encode_varint};
+    use crate::VerifyingKey;
+    use alloc::{string::String, vec::Vec};
+    use elliptic_curve::dev::MockCurve;
+
+    /// Public key from RFC 6979 Appendix 2.5 (NIST P-256), compressed
+    const COMPRESSED_KEY_HEX: &str =
+        "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+
+    #[test]
+    fn sec1_hex_roundtrip() {
+        let key = VerifyingKey::<MockCurve>::from_sec1_hex(COMPRESSED_KEY_HEX).unwrap();
+        assert_eq!(key.to_sec1_hex(true), COMPRESSED_KEY_HEX);
+
+        let upper = COMPRESSED_KEY_HEX.to_uppercase();
+        assert_eq!(
+            VerifyingKey::<MockCurve>::from_sec1_hex(&upper)
+                .unwrap()
+                .to_sec1_hex(true),
+            COMPRESSED_KEY_HEX
+        );
+        assert!(VerifyingKey::<MockCurve>::from_sec1_hex(&COMPRESSED_KEY_HEX[1..]).is_err());
+    }
+
+    #[test]
+    fn multibase_roundtrip() {
+        let key = VerifyingKey::<MockCurve>::from_sec1_hex(COMPRESSED_KEY_HEX).unwrap();
+        let multibase = key.to_multibase();
+        assert_eq!(
+            multibase,
+            "zDnaepBuvsQ8cpsWrVKw8fbpGpvPeNSjVPTWoq6cRqaYzBKVP"
+        );
+        assert_eq!(
+            VerifyingKey::<MockCurve>::from_multibase(&multibase)
+                .unwrap()
+                .to_sec1_hex(true),
+            COMPRESSED_KEY_HEX
+        );
+        assert!(VerifyingKey::<MockCurve>::from_multibase(&multibase[1..]).is_err());
+    }
+
+    #[test]
+    fn multibase_rejects_overlong_input() {
+        let multibase = String::from("z") + &"2".repeat(100_000);
+        assert!(VerifyingKey::<MockCurve>::from_multibase(&multibase).is_err());
+    }
+
+    #[test]
+    fn base58() {
+        let mut encoded = String::new();
+        encode_base58(&[0, 0, 0x28, 0x7f, 0xb4, 0xcd], &mut encoded);
+        assert_eq!(encoded, "11233QC4");
+        assert_eq!(
+            decode_base58(&encoded).unwrap(),
+            [0, 0, 0x28, 0x7f, 0xb4, 0xcd]
+        );
+        assert!(decode_base58("0OIl").is_err());
+    }
+
+    #[test]
+    fn varint() {
+        let mut bytes = Vec::new();
+        encode_varint(0x1200, &mut bytes);
+        assert_eq!(bytes, [0x80, 0x24]);
+        assert_eq!(
+            decode_varint(&[0x80, 0x24, 0x01]).unwrap(),
+            (0x1200, &[0x01][..])
+        );
+        assert!(decode_varint(&[0x80, 0x00]).is_err());
+        assert!(decode_varint(&[0x80]).is_err());
+    }
+}
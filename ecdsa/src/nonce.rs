@@ -0,0 +1,433 @@
+//! Pluggable strategies for generating the ECDSA ephemeral scalar (`k`).
+//!
+//! [`SigningKey`] uses [`Rfc6979`] by default. Protocols with specific nonce
+//! requirements can sign using any [`NonceGenerator`] via
+//! [`SigningKey::try_sign_digest_with_nonce`].
+//!
+//! [`SigningKey`]: crate::SigningKey
+//! [`SigningKey::try_sign_digest_with_nonce`]: crate::SigningKey::try_sign_digest_with_nonce
+
+use crate::{rfc6979, sign, Error, Result, RngError};
+use core::marker::PhantomData;
+use elliptic_curve::{
+    ops::Invert,
+    weierstrass::Curve,
+    zeroize::{Zeroize, Zeroizing},
+    FieldBytes, NonZeroScalar, ProjectiveArithmetic, Scalar,
+};
+use signature::{
+    digest::{BlockInput, FixedOutput, Reset, Update},
+    rand_core::{CryptoRng, RngCore},
+};
+
+#[cfg(feature = "nonce-guard")]
+use {elliptic_curve::group::ff::PrimeField, sha2::Sha256, std::collections::HashMap};
+
+/// Strategy for generating the ephemeral scalar (`k`) used to compute a
+/// signature.
+///
+/// # ⚠️ Security Warning
+///
+/// Reusing `k` for two different messages, or using a `k` which an attacker
+/// can predict or bias, reveals the secret key.
+pub trait NonceGenerator<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    /// Generate `k` for signing the message prehash `msg_scalar` (already
+    /// reduced to a scalar) with `secret_scalar`.
+    fn generate_nonce(
+        &mut self,
+        secret_scalar: &NonZeroScalar<C>,
+        msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>>;
+}
+
+/// Deterministic `k` as described in RFC 6979 (Section 3.2), using `D` as
+/// the HMAC digest.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rfc6979<D> {
+    digest: PhantomData<D>,
+}
+
+impl<D> Rfc6979<D> {
+    /// Create a new RFC 6979 nonce generator.
+    pub fn new() -> Self {
+        Self {
+            digest: PhantomData,
+        }
+    }
+}
+
+impl<C, D> NonceGenerator<C> for Rfc6979<D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        secret_scalar: &NonZeroScalar<C>,
+        msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        Ok(rfc6979::generate_k_prehashed::<C, D>(
+            secret_scalar,
+            msg_scalar,
+            &[],
+        ))
+    }
+}
+
+/// RFC 6979 `k` with additional entropy from an RNG supplied as the
+/// `additional_data` described in Section 3.6, using `D` as the HMAC digest.
+///
+/// Prefer [`HedgedRfc6979`], which places the entropy so as to also protect
+/// against fault and side-channel attacks.
+#[derive(Debug)]
+pub struct Rfc6979WithEntropy<D, R> {
+    rng: R,
+    digest: PhantomData<D>,
+}
+
+impl<D, R> Rfc6979WithEntropy<D, R>
+where
+    R: CryptoRng + RngCore,
+{
+    /// Create a new nonce generator which draws entropy from `rng`.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            digest: PhantomData,
+        }
+    }
+}
+
+impl<C, D, R> NonceGenerator<C> for Rfc6979WithEntropy<D, R>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    R: CryptoRng + RngCore,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        secret_scalar: &NonZeroScalar<C>,
+        msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        let mut added_entropy = FieldBytes::<C>::default();
+        self.rng
+            .try_fill_bytes(&mut added_entropy)
+            .map_err(|_| RngError)?;
+
+        let k = rfc6979::generate_k_prehashed::<C, D>(secret_scalar, msg_scalar, &added_entropy);
+        added_entropy.zeroize();
+        Ok(k)
+    }
+}
+
+/// Hedged `k` as described in `draft-irtf-cfrg-det-sigs-with-noise`, i.e.
+/// RFC 6979 with random noise drawn from an RNG inserted ahead of the secret
+/// scalar, using `D` as the HMAC digest.
+///
+/// The noise is the same size as a field element. This is the construction
+/// used by [`RandomizedDigestSigner`].
+///
+/// [`RandomizedDigestSigner`]: signature::RandomizedDigestSigner
+#[derive(Debug)]
+pub struct HedgedRfc6979<D, R> {
+    rng: R,
+    digest: PhantomData<D>,
+}
+
+impl<D, R> HedgedRfc6979<D, R>
+where
+    R: CryptoRng + RngCore,
+{
+    /// Create a new nonce generator which draws noise from `rng`.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            digest: PhantomData,
+        }
+    }
+}
+
+impl<C, D, R> NonceGenerator<C> for HedgedRfc6979<D, R>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    R: CryptoRng + RngCore,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        secret_scalar: &NonZeroScalar<C>,
+        msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        let mut noise = FieldBytes::<C>::default();
+        self.rng.try_fill_bytes(&mut noise).map_err(|_| RngError)?;
+
+        let k = rfc6979::generate_k_hedged::<C, D>(secret_scalar, msg_scalar, &noise);
+        noise.zeroize();
+        Ok(k)
+    }
+}
+
+/// Uniformly random `k` drawn from an RNG, as described in FIPS 186-4.
+///
+/// Unlike the RFC 6979 generators, a failing or low-quality RNG directly
+/// compromises the secret key.
+#[derive(Debug)]
+pub struct RandomNonce<R> {
+    rng: R,
+}
+
+impl<R> RandomNonce<R>
+where
+    R: CryptoRng + RngCore,
+{
+    /// Create a new nonce generator which draws `k` from `rng`.
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<C, R> NonceGenerator<C> for RandomNonce<R>
+where
+    C: Curve + ProjectiveArithmetic,
+    R: CryptoRng + RngCore,
+    Scalar<C>: Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        _secret_scalar: &NonZeroScalar<C>,
+        _msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        sign::try_random_scalar::<C>(&mut self.rng).map(Zeroizing::new)
+    }
+}
+
+/// Externally supplied `k`, e.g. one computed by a multi-party protocol.
+///
+/// The value can be used for at most one signature: subsequent attempts to
+/// generate a nonce return an error.
+pub struct ExternalNonce<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    k: Option<Zeroizing<NonZeroScalar<C>>>,
+}
+
+impl<C> ExternalNonce<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    /// Wrap the given `k`.
+    pub fn new(k: NonZeroScalar<C>) -> Self {
+        Self {
+            k: Some(Zeroizing::new(k)),
+        }
+    }
+}
+
+impl<C> NonceGenerator<C> for ExternalNonce<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        _secret_scalar: &NonZeroScalar<C>,
+        _msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        self.k.take().ok_or_else(Error::new)
+    }
+}
+
+/// Random ephemeral scalar (`k`) and the signature component `r` computed
+/// from it in advance of signing, e.g. while a signer is idle.
+///
+/// Created using [`SigningKey::precompute_nonce`] and consumed by
+/// [`SigningKey::try_sign_with_precomputed_nonce`], so each can be used for at
+/// most one signature. Both values are zeroized on drop.
+///
+/// [`SigningKey::precompute_nonce`]: crate::SigningKey::precompute_nonce
+/// [`SigningKey::try_sign_with_precomputed_nonce`]: crate::SigningKey::try_sign_with_precomputed_nonce
+pub struct PrecomputedNonce<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    /// Ephemeral scalar
+    pub(crate) k: Scalar<C>,
+
+    /// `r` component of the signature made using `k`
+    pub(crate) r: Scalar<C>,
+}
+
+impl<C> Drop for PrecomputedNonce<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: Zeroize,
+{
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.r.zeroize();
+    }
+}
+
+/// Wrapper which checks that a [`NonceGenerator`] never produces the same
+/// `k` for two different messages, returning an error instead of a nonce
+/// which would reveal the secret key if used.
+///
+/// This is a safety net for custom RNGs and nonce generators: it keeps a
+/// SHA-256 commitment to every `k` generated (and to the message it was
+/// generated for) for the lifetime of the guard, so memory use grows with
+/// the number of signatures. Sharing one guard across all signing in a
+/// process extends the check to the whole process.
+#[cfg(feature = "nonce-guard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "nonce-guard")))]
+#[derive(Debug)]
+pub struct NonceGuard<G> {
+    /// Generator whose nonces are checked
+    inner: G,
+
+    /// Commitments to each `k` generated so far, and to its message
+    seen: HashMap<[u8; 32], [u8; 32]>,
+}
+
+#[cfg(feature = "nonce-guard")]
+impl<G> NonceGuard<G> {
+    /// Check the nonces generated by `inner`.
+    pub fn new(inner: G) -> Self {
+        Self {
+            inner,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Get the number of distinct nonces generated so far.
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Have no nonces been generated yet?
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+}
+
+#[cfg(feature = "nonce-guard")]
+impl<C, G> NonceGenerator<C> for NonceGuard<G>
+where
+    C: Curve + ProjectiveArithmetic,
+    G: NonceGenerator<C>,
+    Scalar<C>: Zeroize,
+{
+    fn generate_nonce(
+        &mut self,
+        secret_scalar: &NonZeroScalar<C>,
+        msg_scalar: &Scalar<C>,
+    ) -> Result<Zeroizing<NonZeroScalar<C>>> {
+        let k = self.inner.generate_nonce(secret_scalar, msg_scalar)?;
+
+        let mut k_bytes = k.to_repr();
+        let k_commitment = commit(b"k", &k_bytes);
+        k_bytes.zeroize();
+
+        let msg_commitment = commit(b"msg", &msg_scalar.to_repr());
+
+        // Keep the first message seen with each `k`, so a reused `k` is
+        // rejected for every other message rather than only the next one
+        if *self.seen.entry(k_commitment).or_insert(msg_commitment) == msg_commitment {
+            Ok(k)
+        } else {
+            Err(Error::new())
+        }
+    }
+}
+
+/// Compute a SHA-256 commitment to `bytes` with the given domain separator.
+#[cfg(feature = "nonce-guard")]
+fn commit(domain: &[u8], bytes: &[u8]) -> [u8; 32] {
+    Sha256::default()
+        .chain([domain.len() as u8])
+        .chain(domain)
+        .chain(bytes)
+        .finalize_fixed()
+        .into()
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use super::{ExternalNonce, NonceGenerator, Rfc6979};
+    use crate::hazmat::FromDigest;
+    use elliptic_curve::{
+        dev::{MockCurve, NonZeroScalar, Scalar},
+        group::ff::PrimeField,
+    };
+    use hex_literal::hex;
+    use sha2::{Digest, Sha256};
+
+    /// Secret key from RFC 6979 Appendix 2.5 (NIST P-256)
+    const SECRET_KEY: [u8; 32] =
+        hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+
+    #[test]
+    fn rfc6979_test_vector() {
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let z = Scalar::from_digest(Sha256::new().chain("sample"));
+        let k = NonceGenerator::<MockCurve>::generate_nonce(&mut Rfc6979::<Sha256>::new(), &x, &z)
+            .unwrap();
+
+        assert_eq!(
+            k.to_repr().as_slice(),
+            &hex!("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60")[..]
+        );
+    }
+
+    #[test]
+    fn external_nonce_is_single_use() {
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let z = Scalar::from_digest(Sha256::new().chain("sample"));
+        let mut nonce = ExternalNonce::new(x);
+
+        assert!(nonce.generate_nonce(&x, &z).is_ok());
+        assert!(nonce.generate_nonce(&x, &z).is_err());
+    }
+
+    #[cfg(feature = "nonce-guard")]
+    #[test]
+    fn nonce_guard_rejects_reuse() {
+        use super::{NonceGuard, Result};
+        use elliptic_curve::zeroize::Zeroizing;
+
+        /// Broken generator which always returns the same `k`
+        struct FixedNonce(NonZeroScalar);
+
+        impl NonceGenerator<MockCurve> for FixedNonce {
+            fn generate_nonce(
+                &mut self,
+                _secret_scalar: &NonZeroScalar,
+                _msg_scalar: &Scalar,
+            ) -> Result<Zeroizing<NonZeroScalar>> {
+                Ok(Zeroizing::new(self.0))
+            }
+        }
+
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let sample = Scalar::from_digest(Sha256::new().chain("sample"));
+        let test = Scalar::from_digest(Sha256::new().chain("test"));
+        let mut guard = NonceGuard::new(FixedNonce(x));
+
+        assert!(guard.generate_nonce(&x, &sample).is_ok());
+        assert!(guard.generate_nonce(&x, &sample).is_ok());
+        assert!(guard.generate_nonce(&x, &test).is_err());
+        assert!(guard.generate_nonce(&x, &test).is_err());
+        assert!(guard.generate_nonce(&x, &sample).is_ok());
+        assert_eq!(guard.len(), 1);
+    }
+}
@@ -0,0 +1,194 @@
+//! Support for ECDSA signatures encoded as OpenPGP multiprecision integers
+//! (MPIs).
+//!
+//! OpenPGP signature packets encode the algorithm-specific fields of an ECDSA
+//! signature as two MPIs, `r` followed by `s` ([RFC 6637 Section 5]). Each MPI
+//! is a two-octet big endian bit count followed by the big endian value with
+//! leading zero octets removed ([RFC 4880 Section 3.2]).
+//!
+//! [RFC 6637 Section 5]: https://tools.ietf.org/html/rfc6637#section-5
+//! [RFC 4880 Section 3.2]: https://tools.ietf.org/html/rfc4880#section-3.2
+
+use crate::{Error, Result, Signature, SignatureSize};
+use core::convert::TryFrom;
+use elliptic_curve::{
+    bigint::Encoding as _, generic_array::ArrayLength, weierstrass::Curve, FieldBytes,
+};
+
+impl<C> Signature<C>
+where
+    C: Curve,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Parse a signature from the `r` and `s` MPIs of an OpenPGP signature
+    /// packet.
+    ///
+    /// The input must contain exactly two MPIs, each of which must be
+    /// minimally encoded.
+    pub fn from_pgp_mpis(bytes: &[u8]) -> Result<Self> {
+        let (r, bytes) = decode_mpi(bytes)?;
+        let (s, bytes) = decode_mpi(bytes)?;
+
+        if !bytes.is_empty() || r.len() > C::UInt::BYTE_SIZE || s.len() > C::UInt::BYTE_SIZE {
+            return Err(Error::new());
+        }
+
+        let mut r_bytes = FieldBytes::<C>::default();
+        let mut s_bytes = FieldBytes::<C>::default();
+        r_bytes[(C::UInt::BYTE_SIZE - r.len())..].copy_from_slice(r);
+        s_bytes[(C::UInt::BYTE_SIZE - s.len())..].copy_from_slice(s);
+        Self::from_scalars(r_bytes, s_bytes)
+    }
+
+    /// Serialize this signature as the `r` and `s` MPIs of an OpenPGP
+    /// signature packet, writing them into the provided buffer.
+    ///
+    /// Returns the written portion of the buffer, which must be at least
+    /// 4-bytes larger than the fixed-size signature encoding.
+    pub fn encode_pgp_mpis<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        let (r, s) = self.as_ref().split_at(C::UInt::BYTE_SIZE);
+        let r_len = encode_mpi(r, buf)?;
+        let s_len = encode_mpi(s, &mut buf[r_len..])?;
+        Ok(&buf[..(r_len + s_len)])
+    }
+}
+
+/// Decode an MPI from the front of the given bytes, returning its value and
+/// the remaining input.
+fn decode_mpi(bytes: &[u8]) -> Result<(&[u8], &[u8])> {
+    if bytes.len() < 2 {
+        return Err(Error::new());
+    }
+
+    let bits = usize::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+    let len = bits / 8 + usize::from(bits % 8 != 0);
+    let bytes = &bytes[2..];
+
+    if bytes.len() < len {
+        return Err(Error::new());
+    }
+
+    let (value, rest) = bytes.split_at(len);
+
+    if value.first().map_or(0, |&byte| mpi_bits(byte, len)) != bits {
+        return Err(Error::new());
+    }
+
+    Ok((value, rest))
+}
+
+/// Encode the given big endian value as an MPI, returning its encoded length.
+fn encode_mpi(value: &[u8], buf: &mut [u8]) -> Result<usize> {
+    let value = &value[value.iter().take_while(|&&byte| byte == 0).count()..];
+    let bits = value.first().map_or(0, |&byte| mpi_bits(byte, value.len()));
+    let bits = u16::try_from(bits).map_err(|_| Error::new())?;
+    let encoded_len = 2 + value.len();
+
+    if buf.len() < encoded_len {
+        return Err(Error::new());
+    }
+
+    buf[..2].copy_from_slice(&bits.to_be_bytes());
+    buf[2..encoded_len].copy_from_slice(value);
+    Ok(encoded_len)
+}
+
+/// Bit length of a `len`-byte big endian value beginning with `first_byte`.
+fn mpi_bits(first_byte: u8, len: usize) -> usize {
+    (len * 8).saturating_sub(first_byte.leading_zeros() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_mpi, encode_mpi};
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    type Signature = crate::Signature<MockCurve>;
+
+    const R: [u8; 32] = hex!("00d48b2a0b8f8fa3ab6b8e94bbd3fa6f8d1e3a8f0a8c8e53f3e1b2f1d4c3b2a1");
+    const S: [u8; 32] = hex!("7cb1c9466d5a95e8e8e0ddc9c3c1a2b3c4d5e6f708192a3b4c5d6e7f80910203");
+
+    #[test]
+    fn pgp_mpi_roundtrip() {
+        let signature = Signature::from_scalars(R, S).unwrap();
+        let mut buf = [0u8; 68];
+        let mpis = signature.encode_pgp_mpis(&mut buf).unwrap();
+
+        // `r` has a leading zero byte and 0xd4 has no leading zero bits
+        assert_eq!(&mpis[..2], &[0x00, 0xf8]);
+        assert_eq!(&mpis[2..33], &R[1..]);
+
+        // `s` is 255 bits
+        assert_eq!(&mpis[33..35], &[0x00, 0xff]);
+        assert_eq!(&mpis[35..], &S[..]);
+
+        assert_eq!(Signature::from_pgp_mpis(mpis).unwrap(), signature);
+    }
+
+    #[test]
+    fn rejects_malformed_pgp_mpis() {
+        let signature = Signature::from_scalars(R, S).unwrap();
+        let mut buf = [0u8; 68];
+        let len = signature.encode_pgp_mpis(&mut buf).unwrap().len();
+
+        // Incorrect bit count
+        let mut mpis = buf;
+        mpis[1] = 0xf7;
+        assert!(Signature::from_pgp_mpis(&mpis[..len]).is_err());
+
+        // Trailing data
+        assert!(Signature::from_pgp_mpis(&buf[..(len + 1)]).is_err());
+
+        // Truncated
+        assert!(Signature::from_pgp_mpis(&buf[..(len - 1)]).is_err());
+
+        // Output buffer too small
+        assert!(signature.encode_pgp_mpis(&mut [0u8; 64]).is_err());
+    }
+
+    #[test]
+    fn mpi_helpers() {
+        let mut buf = [0xffu8; 4];
+
+        // Zero is encoded as a bit count of zero with no value octets
+        assert_eq!(encode_mpi(&[0x00, 0x00], &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[0x00, 0x00]);
+        assert_eq!(
+            decode_mpi(&[0x00, 0x00, 0xaa]).unwrap(),
+            (&[][..], &[0xaa][..])
+        );
+
+        // Leading zero octets are stripped and leading zero bits are not counted
+        assert_eq!(encode_mpi(&[0x00, 0x01, 0xff], &mut buf).unwrap(), 4);
+        assert_eq!(buf, [0x00, 0x09, 0x01, 0xff]);
+        assert_eq!(decode_mpi(&buf).unwrap(), (&[0x01, 0xff][..], &[][..]));
+
+        // Output buffer too small
+        assert!(encode_mpi(&[0x01, 0xff], &mut buf[..3]).is_err());
+
+        // Non-minimal encodings
+        assert!(decode_mpi(&[0x00, 0x08, 0x00]).is_err());
+        assert!(decode_mpi(&[0x00, 0x10, 0x00, 0xff]).is_err());
+
+        // Truncated
+        assert!(decode_mpi(&[0x00]).is_err());
+        assert!(decode_mpi(&[0xff, 0xff, 0x01]).is_err());
+    }
+
+    #[test]
+    fn rejects_zero_length_and_oversized_pgp_mpis() {
+        let mut buf = [0u8; 72];
+
+        // Zero-length `r`, which is not a valid scalar
+        buf[..2].copy_from_slice(&[0x00, 0x00]);
+        let s_len = encode_mpi(&S, &mut buf[2..]).unwrap();
+        assert!(Signature::from_pgp_mpis(&buf[..(2 + s_len)]).is_err());
+
+        // 33-byte `r`, which is longer than a scalar
+        buf[..3].copy_from_slice(&[0x01, 0x01, 0x01]);
+        buf[3..35].copy_from_slice(&R);
+        let s_len = encode_mpi(&S, &mut buf[35..]).unwrap();
+        assert!(Signature::from_pgp_mpis(&buf[..(35 + s_len)]).is_err());
+    }
+}
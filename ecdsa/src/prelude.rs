@@ -0,0 +1,19 @@
+//! Commonly used traits, re-exported for convenience.
+//!
+//! ```
+//! use ecdsa::prelude::*;
+//! ```
+
+pub use crate::signature::{RandomizedSigner, Signature as _, Signer, Verifier};
+
+#[cfg(feature = "digest")]
+pub use crate::signature::{DigestSigner, DigestVerifier, RandomizedDigestSigner};
+
+#[cfg(feature = "arithmetic")]
+pub use crate::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+
+#[cfg(feature = "pkcs8")]
+pub use crate::elliptic_curve::pkcs8::{FromPrivateKey, FromPublicKey};
+
+#[cfg(feature = "pem")]
+pub use crate::elliptic_curve::pkcs8::{ToPrivateKey, ToPublicKey};
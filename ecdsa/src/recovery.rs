@@ -0,0 +1,207 @@
+//! Public key recovery support.
+
+#[cfg(feature = "verify")]
+use {
+    crate::{
+        hazmat::{DigestPrimitive, FromDigest, VerifyPrimitive},
+        Error, Result, Signature, SignatureSize, VerifyingKey,
+    },
+    core::ops::Add,
+    elliptic_curve::{
+        bigint::ArrayEncoding,
+        consts::U1,
+        generic_array::{typenum::Unsigned, ArrayLength, GenericArray},
+        group::{
+            ff::{Field, PrimeField},
+            Curve as _, Group,
+        },
+        sec1::{
+            EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize,
+            UntaggedPointSize,
+        },
+        weierstrass::Curve,
+        AffinePoint, FieldBytes, FieldSize, ProjectiveArithmetic, ProjectivePoint, Scalar,
+    },
+    signature::digest::Digest,
+};
+
+/// Recovery ID for a signature, i.e. the information needed to recover the
+/// signer's public key from the signature and message alongside `r`.
+///
+/// Encoded as a single byte (as used by e.g. Ethereum), where bit 0 is set if
+/// the y-coordinate of 𝐑 is odd and bit 1 is set if the x-coordinate of 𝐑 was
+/// reduced modulo the curve order to obtain `r`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+pub struct RecoveryId(u8);
+
+impl RecoveryId {
+    /// Maximum encoded value of a [`RecoveryId`].
+    pub const MAX: u8 = 3;
+
+    /// Create a new [`RecoveryId`] from the following flags:
+    ///
+    /// - `is_y_odd`: is the y-coordinate of 𝐑 odd?
+    /// - `is_x_reduced`: did the x-coordinate of 𝐑 overflow the curve order?
+    pub const fn new(is_y_odd: bool, is_x_reduced: bool) -> Self {
+        Self((is_x_reduced as u8) << 1 | (is_y_odd as u8))
+    }
+
+    /// Is the y-coordinate of 𝐑 odd?
+    pub const fn is_y_odd(self) -> bool {
+        self.0 & 1 != 0
+    }
+
+    /// Did the x-coordinate of 𝐑 overflow the curve order?
+    pub const fn is_x_reduced(self) -> bool {
+        self.0 & 0b10 != 0
+    }
+
+    /// Decode a [`RecoveryId`] from its byte encoding.
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        if byte <= Self::MAX {
+            Some(Self(byte))
+        } else {
+            None
+        }
+    }
+
+    /// Encode this [`RecoveryId`] as a byte.
+    pub const fn to_byte(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<RecoveryId> for u8 {
+    fn from(recovery_id: RecoveryId) -> u8 {
+        recovery_id.0
+    }
+}
+
+#[cfg(feature = "verify")]
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Recover the [`VerifyingKey`] which produced `signature` over `msg`,
+    /// hashed using the curve's preferred [`Digest`].
+    pub fn recover_from_msg(
+        msg: &[u8],
+        signature: &Signature<C>,
+        recovery_id: RecoveryId,
+    ) -> Result<Self>
+    where
+        C: DigestPrimitive,
+    {
+        Self::recover_from_digest(C::Digest::new().chain(msg), signature, recovery_id)
+    }
+
+    /// Recover the [`VerifyingKey`] which produced `signature` over the
+    /// message hashed into `digest`.
+    ///
+    /// Returns an error if no point 𝐑 corresponds to `r` and the
+    /// [`RecoveryId`]. Note that any other signature, message or
+    /// [`RecoveryId`] generally recovers a *different* key, for which the
+    /// signature is valid, rather than an error: callers must check the
+    /// recovered key against the expected signer.
+    pub fn recover_from_digest<D>(
+        digest: D,
+        signature: &Signature<C>,
+        recovery_id: RecoveryId,
+    ) -> Result<Self>
+    where
+        D: Digest,
+    {
+        let z = Scalar::<C>::from_digest(digest);
+        let (r, s) = signature.split_scalars();
+
+        // Reconstruct 𝐑 from `r` and the recovery ID
+        let mut x = r.to_repr();
+
+        if recovery_id.is_x_reduced() {
+            add_order::<C>(&mut x)?;
+        }
+
+        let mut encoded_point = GenericArray::<u8, UncompressedPointSize<C>>::default();
+        encoded_point[0] = 0x02 | recovery_id.is_y_odd() as u8;
+        encoded_point[1..][..x.len()].copy_from_slice(&x);
+
+        let encoded_point =
+            EncodedPoint::<C>::from_bytes(&encoded_point[..FieldSize::<C>::USIZE + 1])
+                .map_err(|_| Error::new())?;
+        let big_r = AffinePoint::<C>::from_encoded_point(&encoded_point).ok_or_else(Error::new)?;
+
+        // 𝐐 = r⁻¹(s𝐑 - z𝐆)
+        let r_inv = Option::<Scalar<C>>::from(r.invert()).ok_or_else(Error::new)?;
+        let q = ProjectivePoint::<C>::from(big_r) * (*s * r_inv)
+            - ProjectivePoint::<C>::generator() * (z * r_inv);
+
+        let verifying_key = Self::from_affine(q.to_affine())?;
+        verifying_key.as_affine().verify_prehashed(&z, signature)?;
+
+        Ok(verifying_key)
+    }
+}
+
+/// Add the curve order to the big endian integer `x`, as the x-coordinate
+/// of 𝐑 when it was reduced to obtain `r`.
+///
+/// Returns an error if the sum overflows the size of a field element.
+#[cfg(feature = "verify")]
+fn add_order<C: Curve>(x: &mut FieldBytes<C>) -> Result<()> {
+    let order = C::ORDER.to_be_byte_array();
+    let mut carry = 0;
+
+    for (x_byte, order_byte) in x.iter_mut().zip(order.iter()).rev() {
+        let sum = u16::from(*x_byte) + u16::from(*order_byte) + carry;
+        *x_byte = sum as u8;
+        carry = sum >> 8;
+    }
+
+    if carry == 0 {
+        Ok(())
+    } else {
+        Err(Error::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RecoveryId;
+
+    #[test]
+    fn byte_encoding() {
+        for byte in 0..=RecoveryId::MAX {
+            let recovery_id = RecoveryId::from_byte(byte).expect("invalid recovery ID");
+            assert_eq!(recovery_id.to_byte(), byte);
+            assert_eq!(
+                RecoveryId::new(recovery_id.is_y_odd(), recovery_id.is_x_reduced()),
+                recovery_id
+            );
+        }
+
+        assert!(RecoveryId::new(true, false).is_y_odd());
+        assert!(RecoveryId::new(false, true).is_x_reduced());
+        assert_eq!(RecoveryId::from_byte(4), None);
+    }
+    #[cfg(all(feature = "dev", feature = "verify"))]
+    #[test]
+    fn add_order() {
+        use elliptic_curve::{dev::MockCurve, FieldBytes};
+        use hex_literal::hex;
+
+        let mut x = FieldBytes::<MockCurve>::default();
+        x[31] = 1;
+        super::add_order::<MockCurve>(&mut x).unwrap();
+        assert_eq!(
+            x.as_slice(),
+            &hex!("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632552")[..]
+        );
+
+        assert!(super::add_order::<MockCurve>(&mut x).is_err());
+    }
+}
@@ -3,17 +3,20 @@
 //! Implementation of the algorithm described in RFC 6979 (Section 3.2):
 //! <https://tools.ietf.org/html/rfc6979#section-3>
 
-use crate::hazmat::FromDigest;
+use crate::hazmat::{bits2field, FromDigest};
 use elliptic_curve::{
-    generic_array::GenericArray,
+    generic_array::{sequence::GenericSequence, typenum::Unsigned, GenericArray},
     group::ff::PrimeField,
     ops::Invert,
     weierstrass::Curve,
     zeroize::{Zeroize, Zeroizing},
-    FieldBytes, FieldSize, NonZeroScalar, ProjectiveArithmetic, Scalar,
+    FieldBytes, NonZeroScalar, ProjectiveArithmetic, Scalar,
 };
 use hmac::{Hmac, Mac, NewMac};
+
 use signature::digest::{BlockInput, FixedOutput, Reset, Update};
+#[cfg(feature = "rfc6979-audit")]
+use {alloc::vec::Vec, core::fmt};
 
 /// Generate ephemeral scalar `k` from the secret scalar and a digest of the
 /// input message.
@@ -24,23 +27,311 @@ pub fn generate_k<C, D>(
 ) -> Zeroizing<NonZeroScalar<C>>
 where
     C: Curve + ProjectiveArithmetic,
-    D: FixedOutput<OutputSize = FieldSize<C>> + BlockInput + Clone + Default + Reset + Update,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
     Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + Zeroize,
+{
+    generate_k_prehashed::<C, D>(
+        secret_scalar,
+        &Scalar::<C>::from_digest(msg_digest),
+        additional_data,
+    )
+}
+
+/// Generate ephemeral scalar `k` from the secret scalar and a message prehash
+/// which has already been converted to a scalar (i.e. `h1` in RFC 6979),
+/// using `D` as the HMAC digest.
+pub fn generate_k_prehashed<C, D>(
+    secret_scalar: &NonZeroScalar<C>,
+    hashed_msg: &Scalar<C>,
+    additional_data: &[u8],
+) -> Zeroizing<NonZeroScalar<C>>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
 {
     let mut x = secret_scalar.to_repr();
-    let h1 = Scalar::<C>::from_digest(msg_digest).to_repr();
-    let mut hmac_drbg = HmacDrbg::<D>::new(&x, &h1, additional_data);
+    let h1 = hashed_msg.to_repr();
+    let mut hmac_drbg = HmacDrbg::<D>::new(&x, &h1, additional_data, &mut ());
     x.zeroize();
+    hmac_drbg.generate_k::<C>(&mut ())
+}
+
+/// Generate a hedged ephemeral scalar `k` from the secret scalar, a message
+/// prehash which has already been converted to a scalar, and random `noise`,
+/// using `D` as the HMAC digest.
+///
+/// This is the construction described in `draft-irtf-cfrg-det-sigs-with-noise`,
+/// which modifies steps 3.2.d and 3.2.f of RFC 6979 to insert the noise
+/// ahead of the secret scalar, zero-padding so that the secret scalar and
+/// message prehash each start a new HMAC block.
+pub fn generate_k_hedged<C, D>(
+    secret_scalar: &NonZeroScalar<C>,
+    hashed_msg: &Scalar<C>,
+    noise: &[u8],
+) -> Zeroizing<NonZeroScalar<C>>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    let mut x = secret_scalar.to_repr();
+    let h1 = hashed_msg.to_repr();
+    let mut hmac_drbg = HmacDrbg::<D>::new_hedged(&x, &h1, noise);
+    x.zeroize();
+    hmac_drbg.generate_k::<C>(&mut ())
+}
+
+/// Recompute `k` as [`generate_k_prehashed`] does, recording the
+/// `HMAC_DRBG` state after each step and every candidate value, so that the
+/// derivation of `k` for a disputed signature can be checked independently.
+///
+/// # ⚠️ Security Warning
+///
+/// The transcript contains `k`, which reveals the secret key when combined
+/// with the corresponding signature. Only compute it where the secret key
+/// could be used directly.
+#[cfg(feature = "rfc6979-audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rfc6979-audit")))]
+pub fn audit_k_prehashed<C, D>(
+    secret_scalar: &NonZeroScalar<C>,
+    hashed_msg: &Scalar<C>,
+    additional_data: &[u8],
+) -> Transcript
+where
+    C: Curve + ProjectiveArithmetic,
+    D: FixedOutput + BlockInput + Clone + Default + Reset + Update,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    let mut transcript = Transcript {
+        states: Vec::new(),
+        candidates: Vec::new(),
+        k: Vec::new(),
+    };
+
+    let mut x = secret_scalar.to_repr();
+    let h1 = hashed_msg.to_repr();
+    let mut hmac_drbg = HmacDrbg::<D>::new(&x, &h1, additional_data, &mut transcript);
+    x.zeroize();
+    hmac_drbg.generate_k::<C>(&mut transcript);
+    transcript
+}
+
+/// Record of the computation of `k` produced by [`audit_k_prehashed`].
+///
+/// All values are zeroized when they are dropped, and are omitted from
+/// [`Debug`][fmt::Debug] output.
+#[cfg(feature = "rfc6979-audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rfc6979-audit")))]
+pub struct Transcript {
+    /// `HMAC_DRBG` state after each step, in order
+    pub states: Vec<DrbgState>,
+
+    /// Candidate values of `T` generated in step 3.2.h, in order
+    pub candidates: Vec<Candidate>,
+
+    /// Current value of `K`, which is only reported when it changes
+    k: Vec<u8>,
+}
+
+/// `HMAC_DRBG` state recorded in a [`Transcript`].
+#[cfg(feature = "rfc6979-audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rfc6979-audit")))]
+#[derive(Clone, Eq, PartialEq)]
+pub struct DrbgState {
+    /// Step of RFC 6979 Section 3.2 after which the state was recorded
+    /// (e.g. `3.2.d`)
+    pub step: &'static str,
+
+    /// HMAC key `K`
+    pub k: Vec<u8>,
+
+    /// Chaining value `V`
+    pub v: Vec<u8>,
+}
 
-    loop {
-        let mut tmp = FieldBytes::<C>::default();
-        hmac_drbg.generate_into(&mut tmp);
-        if let Some(k) = NonZeroScalar::from_repr(tmp) {
-            return Zeroizing::new(k);
+/// Candidate value of `T` recorded in a [`Transcript`].
+#[cfg(feature = "rfc6979-audit")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rfc6979-audit")))]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Candidate {
+    /// Output of `HMAC_DRBG`, before conversion to a scalar
+    pub t: Vec<u8>,
+
+    /// Whether `T` was in the range `[1, n)` and so used as `k`
+    pub accepted: bool,
+}
+
+#[cfg(feature = "rfc6979-audit")]
+impl Observer for Transcript {
+    fn state(&mut self, step: &'static str, k: Option<&[u8]>, v: &[u8]) {
+        if let Some(k) = k {
+            self.k.zeroize();
+            self.k = k.to_vec();
         }
+
+        self.states.push(DrbgState {
+            step,
+            k: self.k.clone(),
+            v: v.to_vec(),
+        });
+    }
+
+    fn candidate(&mut self, t: &[u8], accepted: bool) {
+        self.candidates.push(Candidate {
+            t: t.to_vec(),
+            accepted,
+        });
+    }
+}
+
+#[cfg(feature = "rfc6979-audit")]
+impl fmt::Debug for Transcript {
+    /// Print the steps and candidates, but never `K`, `V` or `T`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transcript")
+            .field("states", &self.states)
+            .field("candidates", &self.candidates)
+            .finish()
+    }
+}
+
+#[cfg(feature = "rfc6979-audit")]
+impl Drop for Transcript {
+    fn drop(&mut self) {
+        self.k.as_mut_slice().zeroize();
+    }
+}
+
+#[cfg(feature = "rfc6979-audit")]
+impl fmt::Debug for DrbgState {
+    /// Print the step, but never `K` or `V`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DrbgState")
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+/// Zeroize `K` and `V`, including in states moved out of a [`Transcript`].
+#[cfg(feature = "rfc6979-audit")]
+impl Drop for DrbgState {
+    fn drop(&mut self) {
+        self.k.as_mut_slice().zeroize();
+        self.v.as_mut_slice().zeroize();
+    }
+}
+
+#[cfg(feature = "rfc6979-audit")]
+impl fmt::Debug for Candidate {
+    /// Print whether the candidate was accepted, but never `T`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Candidate")
+            .field("accepted", &self.accepted)
+            .finish()
+    }
+}
+
+/// Zeroize `T`, including in candidates moved out of a [`Transcript`].
+#[cfg(feature = "rfc6979-audit")]
+impl Drop for Candidate {
+    fn drop(&mut self) {
+        self.t.as_mut_slice().zeroize();
+    }
+}
+
+/// Portion of the RFC 6979 computation of `k` which depends only on the
+/// secret scalar, computed once so it can be reused for many messages signed
+/// with the same key using `D` as the HMAC digest.
+///
+/// This caches the HMAC state in step 3.2.d after absorbing `V || 0x00 || x`,
+/// so only the message prehash needs to be absorbed for each message.
+///
+/// The serialized secret scalar is zeroized on drop, but the cached HMAC
+/// state is not, as `hmac` does not support it.
+pub struct KeySchedule<C, D>
+where
+    C: Curve,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    /// Serialized secret scalar (`x`), which is absorbed again in step 3.2.f
+    x: FieldBytes<C>,
+
+    /// HMAC state in step 3.2.d after absorbing `V || 0x00 || x`
+    step_d: Hmac<D>,
+}
+
+impl<C, D> KeySchedule<C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    Scalar<C>: Invert<Output = Scalar<C>> + Zeroize,
+{
+    /// Compute the key schedule for the given secret scalar.
+    pub fn new(secret_scalar: &NonZeroScalar<C>) -> Self {
+        let x = secret_scalar.to_repr();
+        let mut step_d = Hmac::<D>::new(&Default::default());
+        step_d.update(&HmacDrbg::<D>::initial_v());
+        step_d.update(&[0x00]);
+        step_d.update(&x);
+
+        Self { x, step_d }
+    }
+
+    /// Generate `k` for a message prehash which has already been converted
+    /// to a scalar, i.e. the same value as [`generate_k_prehashed`] with no
+    /// additional data.
+    pub fn generate_k(&self, hashed_msg: &Scalar<C>) -> Zeroizing<NonZeroScalar<C>> {
+        let h1 = hashed_msg.to_repr();
+
+        // Step 3.2.d: K = HMAC_K(V || 0x00 || x || h1)
+        let mut k = self.step_d.clone();
+        k.update(&h1);
+        let mut k = Hmac::<D>::new_from_slice(&k.finalize().into_bytes()).expect("HMAC error");
+
+        // Step 3.2.e: V = HMAC_K(V)
+        k.update(&HmacDrbg::<D>::initial_v());
+        let v = k.finalize_reset().into_bytes();
+
+        // Step 3.2.f: K = HMAC_K(V || 0x01 || x || h1)
+        k.update(&v);
+        k.update(&[0x01]);
+        k.update(&self.x);
+        k.update(&h1);
+        let mut k = Hmac::<D>::new_from_slice(&k.finalize().into_bytes()).expect("HMAC error");
+
+        // Step 3.2.g: V = HMAC_K(V)
+        k.update(&v);
+        let v = k.finalize_reset().into_bytes();
+
+        HmacDrbg { k, v }.generate_k::<C>(&mut ())
     }
 }
 
+impl<C, D> Drop for KeySchedule<C, D>
+where
+    C: Curve,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    fn drop(&mut self) {
+        self.x.zeroize();
+    }
+}
+
+/// Receives the `HMAC_DRBG` state as `k` is computed, e.g. to record it for
+/// auditing.
+trait Observer {
+    /// `K` was set to `k` (or left unchanged, if `None`) and `V` to `v` in
+    /// the given step of RFC 6979 Section 3.2.
+    fn state(&mut self, _step: &'static str, _k: Option<&[u8]>, _v: &[u8]) {}
+
+    /// Candidate `T` was generated in step 3.2.h, and `accepted` as `k` or
+    /// not.
+    fn candidate(&mut self, _t: &[u8], _accepted: bool) {}
+}
+
+impl Observer for () {}
+
 /// Internal implementation of `HMAC_DRBG` as described in NIST SP800-90A:
 /// <https://csrc.nist.gov/publications/detail/sp/800-90a/rev-1/final>
 ///
@@ -63,20 +354,52 @@ where
     D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
 {
     /// Initialize `HMAC_DRBG`
-    pub fn new(entropy_input: &[u8], nonce: &[u8], additional_data: &[u8]) -> Self {
+    fn new(
+        entropy_input: &[u8],
+        nonce: &[u8],
+        additional_data: &[u8],
+        observer: &mut impl Observer,
+    ) -> Self {
         let mut k = Hmac::new(&Default::default());
-        let mut v = GenericArray::default();
+        let mut v = Self::initial_v();
+        let initial_k = GenericArray::<u8, D::OutputSize>::default();
+        observer.state("3.2.c", Some(&initial_k), &v);
+
+        for (i, steps) in [("3.2.d", "3.2.e"), ("3.2.f", "3.2.g")].iter().enumerate() {
+            k.update(&v);
+            k.update(&[i as u8]);
+            k.update(entropy_input);
+            k.update(nonce);
+            k.update(additional_data);
+            let key = k.finalize().into_bytes();
+            observer.state(steps.0, Some(&key), &v);
+            k = Hmac::new_from_slice(&key).expect("HMAC error");
 
-        for b in &mut v {
-            *b = 0x01;
+            // Steps 3.2.e,g: v = HMAC_k(v)
+            k.update(&v);
+            v = k.finalize_reset().into_bytes();
+            observer.state(steps.1, None, &v);
         }
 
+        Self { k, v }
+    }
+
+    /// Initialize `HMAC_DRBG` as modified by `draft-irtf-cfrg-det-sigs-with-noise`,
+    /// i.e. `K = HMAC_K(V || i || noise || 000... || x || 000... || h1)` where
+    /// each `000...` pads to the next HMAC block boundary.
+    pub fn new_hedged(entropy_input: &[u8], nonce: &[u8], noise: &[u8]) -> Self {
+        let block_size = D::BlockSize::to_usize();
+        let mut k = Hmac::new(&Default::default());
+        let mut v = Self::initial_v();
+
         for i in 0..=1 {
             k.update(&v);
             k.update(&[i]);
+            k.update(noise);
+            update_zero_padding(&mut k, v.len() + 1 + noise.len(), block_size);
             k.update(entropy_input);
+            update_zero_padding(&mut k, entropy_input.len(), block_size);
             k.update(nonce);
-            k.update(additional_data);
             k = Hmac::new_from_slice(&k.finalize().into_bytes()).expect("HMAC error");
 
             // Steps 3.2.e,g: v = HMAC_k(v)
@@ -87,44 +410,201 @@ where
         Self { k, v }
     }
 
+    /// Initial chaining value `V = 0x01 0x01 ... 0x01` (see RFC 6979
+    /// Section 3.2.b)
+    fn initial_v() -> GenericArray<u8, D::OutputSize> {
+        GenericArray::generate(|_| 0x01)
+    }
+
+    /// Generate a nonzero scalar, rejecting outputs outside `[1, n)` as
+    /// described in RFC 6979 Section 3.2.h.
+    fn generate_k<C>(&mut self, observer: &mut impl Observer) -> Zeroizing<NonZeroScalar<C>>
+    where
+        C: Curve + ProjectiveArithmetic,
+        Scalar<C>: Zeroize,
+    {
+        loop {
+            let mut tmp = FieldBytes::<C>::default();
+            self.generate_into(&mut tmp, observer);
+            let k = NonZeroScalar::from_repr(bits2field::<C>(&tmp));
+            observer.candidate(&tmp, k.is_some());
+            tmp.zeroize();
+
+            if let Some(k) = k {
+                return Zeroizing::new(k);
+            }
+        }
+    }
+
     /// Get the next `HMAC_DRBG` output
-    pub fn generate_into(&mut self, out: &mut [u8]) {
+    fn generate_into(&mut self, out: &mut [u8], observer: &mut impl Observer) {
         for out_chunk in out.chunks_mut(self.v.len()) {
             self.k.update(&self.v);
             self.v = self.k.finalize_reset().into_bytes();
+            observer.state("3.2.h.2", None, &self.v);
             out_chunk.copy_from_slice(&self.v[..out_chunk.len()]);
         }
 
         self.k.update(&self.v);
         self.k.update(&[0x00]);
-        self.k = Hmac::new_from_slice(&self.k.finalize_reset().into_bytes()).expect("HMAC error");
+        let key = self.k.finalize_reset().into_bytes();
+        observer.state("3.2.h.3", Some(&key), &self.v);
+        self.k = Hmac::new_from_slice(&key).expect("HMAC error");
         self.k.update(&self.v);
         self.v = self.k.finalize_reset().into_bytes();
+        observer.state("3.2.h.3", None, &self.v);
+    }
+}
+
+impl<D> Drop for HmacDrbg<D>
+where
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+{
+    /// Zeroize the chaining value, from which `k` is derived.
+    ///
+    /// The HMAC key state is not zeroized, as `hmac` does not support it.
+    fn drop(&mut self) {
+        self.v.zeroize();
+    }
+}
+
+/// Feed zeroes into `mac` to pad `len` bytes of input to a multiple of
+/// `block_size`.
+fn update_zero_padding<M: Mac>(mac: &mut M, len: usize, block_size: usize) {
+    const ZEROES: [u8; 32] = [0; 32];
+    let mut remaining = (block_size - len % block_size) % block_size;
+
+    while remaining > 0 {
+        let n = remaining.min(ZEROES.len());
+        mac.update(&ZEROES[..n]);
+        remaining -= n;
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::generate_k;
+    use super::{generate_k, BlockInput, FixedOutput, KeySchedule, Reset, Update};
     use elliptic_curve::{dev::NonZeroScalar, group::ff::PrimeField};
     use hex_literal::hex;
-    use sha2::{Digest, Sha256};
+    use sha2::{Sha224, Sha256, Sha384, Sha512};
+
+    /// Secret key from RFC 6979 Appendix 2.5 (NIST P-256)
+    const SECRET_KEY: [u8; 32] =
+        hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
 
-    /// Test vector from RFC 6979 Appendix 2.5 (NIST P-256 + SHA-256)
+    /// Check `k` computed for the message `sample` using the given digest
+    fn check_k<D>(expected_k: [u8; 32])
+    where
+        D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    {
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let k = generate_k(&x, D::default().chain("sample"), &[]);
+        assert_eq!(k.to_repr().as_slice(), &expected_k[..]);
+    }
+
+    /// Test vectors from RFC 6979 Appendix 2.5 (NIST P-256)
     /// <https://tools.ietf.org/html/rfc6979#appendix-A.2.5>
     #[test]
     fn appendix_2_5_test_vector() {
-        let x = NonZeroScalar::from_repr(
-            hex!("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721").into(),
-        )
-        .unwrap();
+        check_k::<Sha256>(hex!(
+            "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60"
+        ));
+    }
+
+    /// Digest shorter than the scalar size
+    #[test]
+    fn appendix_2_5_sha224_test_vector() {
+        check_k::<Sha224>(hex!(
+            "103f90ee9dc52e5e7fb5132b7033c63066d194321491862059967c715985d473"
+        ));
+    }
+
+    /// Digests longer than the scalar size
+    #[test]
+    fn appendix_2_5_sha384_test_vector() {
+        check_k::<Sha384>(hex!(
+            "09f634b188cefd98e7ec88b1aa9852d734d0bc272f7d2a47decc6ebeb375aad4"
+        ));
+    }
+
+    #[test]
+    fn appendix_2_5_sha512_test_vector() {
+        check_k::<Sha512>(hex!(
+            "5fa81c63109badb88c1f367b47da606da28cad69aa22c4fe6ad7df73a7173aa5"
+        ));
+    }
+
+    /// Key schedule gives the same `k` as computing it from scratch
+    #[cfg(feature = "dev")]
+    #[test]
+    fn key_schedule() {
+        use crate::hazmat::FromDigest;
+        use elliptic_curve::dev::{MockCurve, Scalar};
+
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let key_schedule = KeySchedule::<MockCurve, Sha256>::new(&x);
 
-        let digest = Sha256::new().chain("sample");
-        let k = generate_k(&x, digest, &[]);
+        for msg in &["sample", "test"] {
+            let h1 = Scalar::from_digest(Sha256::default().chain(msg));
+            let k = super::generate_k_prehashed::<MockCurve, Sha256>(&x, &h1, &[]);
+            assert_eq!(key_schedule.generate_k(&h1).to_repr(), k.to_repr());
+        }
+    }
+
+    #[cfg(all(feature = "dev", feature = "rfc6979-audit"))]
+    #[test]
+    fn audit_transcript() {
+        use crate::hazmat::FromDigest;
+        use alloc::vec::Vec;
+        use elliptic_curve::dev::{MockCurve, Scalar};
+
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let h1 = Scalar::from_digest(Sha256::default().chain("sample"));
+        let transcript = super::audit_k_prehashed::<MockCurve, Sha256>(&x, &h1, &[]);
+
+        let steps: Vec<_> = transcript.states.iter().map(|state| state.step).collect();
+        assert_eq!(
+            steps,
+            ["3.2.c", "3.2.d", "3.2.e", "3.2.f", "3.2.g", "3.2.h.2", "3.2.h.3", "3.2.h.3"]
+        );
+        assert_eq!(transcript.states[0].k, [0; 32]);
+        assert_eq!(transcript.states[0].v, [1; 32]);
+
+        assert_eq!(transcript.candidates.len(), 1);
+        assert!(transcript.candidates[0].accepted);
+        assert_eq!(
+            transcript.candidates[0].t,
+            hex!("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60")
+        );
+
+        let debug = alloc::format!("{:?}", transcript);
+        assert!(debug.contains("3.2.h.3"));
+        assert!(!debug.contains(" k: ") && !debug.contains(" v: ") && !debug.contains(" t: "));
+    }
+
+    /// Hedged `k` for the message `sample` with noise `00 01 .. 1f`, computed
+    /// using an independent implementation of the construction
+    #[cfg(feature = "dev")]
+    #[test]
+    fn hedged_test_vector() {
+        use crate::hazmat::FromDigest;
+        use elliptic_curve::dev::{MockCurve, Scalar};
+
+        let x = NonZeroScalar::from_repr(SECRET_KEY.into()).unwrap();
+        let h1 = Scalar::from_digest(Sha256::default().chain("sample"));
+        let noise = hex!("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");
+
+        let k = super::generate_k_hedged::<MockCurve, Sha256>(&x, &h1, &noise);
+        assert_eq!(
+            k.to_repr().as_slice(),
+            &hex!("c3e57ca528ad55c535bae4c494eba1518bb72ca23222376e7688db4c1cc15bbc")[..]
+        );
 
+        // Distinct from RFC 6979 `k` even when the noise is empty
+        let k = super::generate_k_hedged::<MockCurve, Sha256>(&x, &h1, &[]);
         assert_eq!(
             k.to_repr().as_slice(),
-            &hex!("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60")[..]
+            &hex!("6d284ba4b0737e50d609dadb91184fae49cd7da3350ccfcbc4746da5488f1b2e")[..]
         );
     }
 }
@@ -0,0 +1,148 @@
+//! Ephemeral signing sessions, and keys prepared for signing many messages.
+
+use crate::{
+    hazmat::{FromDigest, SignPrimitive},
+    rfc6979::KeySchedule,
+    sign, Result, Signature, SignatureSize, SigningKey,
+};
+use elliptic_curve::{
+    generic_array::ArrayLength, ops::Invert, weierstrass::Curve, zeroize::Zeroize,
+    ProjectiveArithmetic, Scalar,
+};
+use signature::{
+    digest::{BlockInput, Digest, FixedOutput, Reset, Update},
+    rand_core::{CryptoRng, RngCore},
+    DigestSigner,
+};
+
+/// Single-use ECDSA signing session.
+///
+/// Owns an ephemeral scalar (`k`) which is generated when the session is
+/// started, e.g. in advance of the message to be signed becoming available,
+/// and which is consumed by signing exactly one message.
+///
+/// The ephemeral scalar is zeroized when the session is dropped, whether
+/// after signing or when it is aborted.
+#[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
+pub struct SigningSession<'a, C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Key used to sign the message
+    signing_key: &'a SigningKey<C>,
+
+    /// Ephemeral scalar (`k`) for this session
+    ephemeral_scalar: Scalar<C>,
+}
+
+impl<'a, C> SigningSession<'a, C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Start a new session for the given key, generating a random
+    /// ephemeral scalar.
+    ///
+    /// Returns [`RngError`] (as the error source) if the RNG fails.
+    ///
+    /// [`RngError`]: crate::RngError
+    pub fn new(signing_key: &'a SigningKey<C>, rng: impl CryptoRng + RngCore) -> Result<Self> {
+        let mut k = sign::try_random_scalar::<C>(rng)?;
+        let ephemeral_scalar = *k;
+        k.zeroize();
+
+        Ok(Self {
+            signing_key,
+            ephemeral_scalar,
+        })
+    }
+
+    /// Sign the given message digest, consuming the session.
+    pub fn try_sign_digest<D>(self, digest: D) -> Result<Signature<C>>
+    where
+        D: Digest,
+    {
+        self.try_sign_prehashed(&Scalar::<C>::from_digest(digest))
+    }
+
+    /// Sign a message which has already been hashed and reduced to a scalar,
+    /// consuming the session.
+    pub fn try_sign_prehashed(self, hashed_msg: &Scalar<C>) -> Result<Signature<C>> {
+        self.signing_key
+            .inner
+            .try_sign_prehashed(&self.ephemeral_scalar, hashed_msg)
+    }
+
+    /// Abort the session without signing, zeroizing its ephemeral scalar.
+    pub fn abort(self) {}
+}
+
+impl<'a, C> Drop for SigningSession<'a, C>
+where
+    C: Curve + ProjectiveArithmetic,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn drop(&mut self) {
+        self.ephemeral_scalar.zeroize();
+    }
+}
+
+/// [`SigningKey`] prepared for signing many message digests of type `D`,
+/// e.g. by a CA or transparency log.
+///
+/// Signatures are identical to those computed by [`SigningKey`], i.e. using
+/// a deterministic ephemeral scalar (`k`) computed as described in RFC 6979,
+/// but the part of that computation which depends only on the key is
+/// performed once when the key is prepared (see [`KeySchedule`]).
+#[cfg_attr(docsrs, doc(cfg(feature = "sign")))]
+pub struct PreparedSigningKey<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Key used to sign messages
+    signing_key: &'a SigningKey<C>,
+
+    /// Precomputed RFC 6979 state for the key
+    key_schedule: KeySchedule<C, D>,
+}
+
+impl<'a, C, D> PreparedSigningKey<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Prepare the given key for signing.
+    pub fn new(signing_key: &'a SigningKey<C>) -> Self {
+        Self {
+            signing_key,
+            key_schedule: KeySchedule::new(&signing_key.inner),
+        }
+    }
+
+    /// Sign a message which has already been hashed and reduced to a scalar.
+    pub fn try_sign_prehashed(&self, hashed_msg: &Scalar<C>) -> Result<Signature<C>> {
+        let k = self.key_schedule.generate_k(hashed_msg);
+        self.signing_key.inner.try_sign_prehashed(&**k, hashed_msg)
+    }
+}
+
+impl<'a, C, D> DigestSigner<D, Signature<C>> for PreparedSigningKey<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: BlockInput + FixedOutput + Clone + Default + Reset + Update,
+    Scalar<C>: FromDigest<C> + Invert<Output = Scalar<C>> + SignPrimitive<C> + Zeroize,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn try_sign_digest(&self, digest: D) -> Result<Signature<C>> {
+        self.try_sign_prehashed(&Scalar::<C>::from_digest(digest))
+    }
+}
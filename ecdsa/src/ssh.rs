@@ -0,0 +1,183 @@
+//! Support for the SSH public key format for ECDSA keys described in
+//! [RFC 5656 Section 3.1].
+//!
+//! Keys can be converted to and from binary SSH public key blobs, or the
+//! OpenSSH text format used in `authorized_keys` and `known_hosts` files,
+//! e.g. `ecdsa-sha2-nistp256 AAAAE2VjZHNh... user@example.com`.
+//!
+//! [RFC 5656 Section 3.1]: https://tools.ietf.org/html/rfc5656#section-3.1
+
+use crate::{Error, Result, VerifyingKey};
+use alloc::{string::String, vec::Vec};
+use base64ct::{Base64, Encoding};
+use core::{convert::TryFrom, ops::Add};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    sec1::{FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+    weierstrass::Curve,
+    AffinePoint, ProjectiveArithmetic,
+};
+
+/// Prefix of the SSH public key algorithm name for ECDSA keys.
+const KEY_TYPE_PREFIX: &str = "ecdsa-sha2-";
+
+/// Identify an elliptic curve in the SSH protocol.
+pub trait SshParameters: Curve {
+    /// Curve identifier as described in [RFC 5656 Section 6.1], e.g.
+    /// `nistp256`.
+    ///
+    /// The SSH public key algorithm name is this identifier prefixed with
+    /// `ecdsa-sha2-`.
+    ///
+    /// [RFC 5656 Section 6.1]: https://tools.ietf.org/html/rfc5656#section-6.1
+    const CURVE_IDENTIFIER: &'static str;
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + SshParameters,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`VerifyingKey`] from an SSH public key blob.
+    ///
+    /// The key type and curve identifier must both match the curve `C`.
+    pub fn from_ssh_public_key_blob(mut blob: &[u8]) -> Result<Self> {
+        let key_type = decode_string(&mut blob)?;
+
+        if key_type.strip_prefix(KEY_TYPE_PREFIX.as_bytes()) != Some(C::CURVE_IDENTIFIER.as_bytes())
+            || decode_string(&mut blob)? != C::CURVE_IDENTIFIER.as_bytes()
+        {
+            return Err(Error::new());
+        }
+
+        let public_key = decode_string(&mut blob)?;
+
+        if !blob.is_empty() {
+            return Err(Error::new());
+        }
+
+        Self::from_sec1_bytes(public_key)
+    }
+
+    /// Parse a [`VerifyingKey`] from a public key in the OpenSSH format,
+    /// i.e. the key type followed by the Base64-encoded key blob and an
+    /// optional comment, which is ignored.
+    ///
+    /// `authorized_keys` lines which begin with options are not supported.
+    pub fn from_openssh_public_key(line: &str) -> Result<Self> {
+        let mut fields = line.split_whitespace();
+        let key_type = fields.next().ok_or_else(Error::new)?;
+
+        if key_type.strip_prefix(KEY_TYPE_PREFIX) != Some(C::CURVE_IDENTIFIER) {
+            return Err(Error::new());
+        }
+
+        let blob =
+            Base64::decode_vec(fields.next().ok_or_else(Error::new)?).map_err(|_| Error::new())?;
+
+        Self::from_ssh_public_key_blob(&blob)
+    }
+
+    /// Serialize this [`VerifyingKey`] as an SSH public key blob, i.e. the
+    /// binary key format used on the wire and in `authorized_keys` files.
+    pub fn to_ssh_public_key_blob(&self) -> Vec<u8> {
+        let key_type_len = KEY_TYPE_PREFIX.len() + C::CURVE_IDENTIFIER.len();
+        let mut blob = Vec::new();
+
+        encode_len(&mut blob, key_type_len);
+        blob.extend_from_slice(KEY_TYPE_PREFIX.as_bytes());
+        blob.extend_from_slice(C::CURVE_IDENTIFIER.as_bytes());
+        encode_string(&mut blob, C::CURVE_IDENTIFIER.as_bytes());
+        encode_string(&mut blob, self.to_encoded_point(false).as_bytes());
+        blob
+    }
+
+    /// Serialize this [`VerifyingKey`] in the OpenSSH public key format,
+    /// without a comment.
+    pub fn to_openssh_public_key(&self) -> String {
+        let mut line = String::from(KEY_TYPE_PREFIX);
+        line.push_str(C::CURVE_IDENTIFIER);
+        line.push(' ');
+        line.push_str(&Base64::encode_string(&self.to_ssh_public_key_blob()));
+        line
+    }
+}
+
+/// Encode a length prefix as a big endian `uint32`.
+fn encode_len(blob: &mut Vec<u8>, len: usize) {
+    let len = u32::try_from(len).expect("SSH string too long");
+    blob.extend_from_slice(&len.to_be_bytes());
+}
+
+/// Encode a length-prefixed SSH `string`.
+fn encode_string(blob: &mut Vec<u8>, bytes: &[u8]) {
+    encode_len(blob, bytes.len());
+    blob.extend_from_slice(bytes);
+}
+
+/// Decode a length-prefixed SSH `string` from the front of `blob`.
+fn decode_string<'a>(blob: &mut &'a [u8]) -> Result<&'a [u8]> {
+    if blob.len() < 4 {
+        return Err(Error::new());
+    }
+
+    let (len, rest) = blob.split_at(4);
+    let len = usize::try_from(u32::from_be_bytes([len[0], len[1], len[2], len[3]]))
+        .map_err(|_| Error::new())?;
+
+    if rest.len() < len {
+        return Err(Error::new());
+    }
+
+    let (string, rest) = rest.split_at(len);
+    *blob = rest;
+    Ok(string)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use crate::VerifyingKey;
+    use elliptic_curve::dev::MockCurve;
+
+    /// Public key from RFC 6979 Appendix A.2.5 (P-256) in the OpenSSH format
+    const OPENSSH_KEY: &str = "ecdsa-sha2-nistp256 \
+        AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBGD+1LolWp0xyWHrdMY1bWjASbiSO2H6bOZpYi5g8p+2eQP+EAi4vJmkGunpVii8ZPLxsgwtfp9Rd6PClNRGIpk=";
+
+    #[test]
+    fn openssh_roundtrip() {
+        let key = VerifyingKey::<MockCurve>::from_openssh_public_key(OPENSSH_KEY).unwrap();
+        assert_eq!(key.to_openssh_public_key(), OPENSSH_KEY);
+
+        let with_comment = [OPENSSH_KEY, " user@example.com\n"].concat();
+        let parsed = VerifyingKey::<MockCurve>::from_openssh_public_key(&with_comment).unwrap();
+        assert_eq!(
+            parsed.to_ssh_public_key_blob(),
+            key.to_ssh_public_key_blob()
+        );
+    }
+
+    #[test]
+    fn reject_malformed_blobs() {
+        let key = VerifyingKey::<MockCurve>::from_openssh_public_key(OPENSSH_KEY).unwrap();
+        let blob = key.to_ssh_public_key_blob();
+
+        for len in &[0, 3, 23, blob.len() - 1] {
+            assert!(VerifyingKey::<MockCurve>::from_ssh_public_key_blob(&blob[..*len]).is_err());
+        }
+
+        // Trailing data
+        let extended = [&blob[..], &[0]].concat();
+        assert!(VerifyingKey::<MockCurve>::from_ssh_public_key_blob(&extended).is_err());
+
+        // Curve identifier which does not match the key type
+        let mut mismatched = blob.clone();
+        mismatched[30] = b'3';
+        assert!(VerifyingKey::<MockCurve>::from_ssh_public_key_blob(&mismatched).is_err());
+
+        let wrong_type = OPENSSH_KEY.replacen("nistp256", "nistp384", 1);
+        assert!(VerifyingKey::<MockCurve>::from_openssh_public_key(&wrong_type).is_err());
+    }
+}
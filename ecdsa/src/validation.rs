@@ -0,0 +1,174 @@
+//! Public key validation with a caller-selected level of strictness.
+
+use crate::{Error, VerifyingKey};
+use core::{fmt, ops::Add};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    group::{cofactor::CofactorGroup, Group},
+    sec1::{
+        EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize,
+    },
+    weierstrass::Curve,
+    AffinePoint, ProjectiveArithmetic, ProjectivePoint, PublicKey,
+};
+
+/// Public key validation to perform on import, as described in
+/// [NIST SP 800-56A Rev. 3] Section 5.6.2.3.
+///
+/// Points which are not on the curve, and the identity, are rejected by both
+/// levels of validation, as no [`VerifyingKey`] can be constructed from them.
+///
+/// [NIST SP 800-56A Rev. 3]: https://csrc.nist.gov/publications/detail/sp/800-56a/rev-3/final
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PublicKeyValidation {
+    /// Partial public key validation: the point must be validly encoded, on
+    /// the curve, and not the identity.
+    Partial,
+
+    /// Full public key validation: partial validation, and the point must
+    /// also be in the prime order subgroup.
+    ///
+    /// This is implied by partial validation on curves with a cofactor of 1,
+    /// such as the NIST P-curves and secp256k1.
+    Full,
+}
+
+/// Reasons a public key can be rejected by [`PublicKeyValidation`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PointValidationError {
+    /// Input is not a valid SEC1 encoding of a point
+    InvalidEncoding,
+
+    /// The point is not on the curve
+    NotOnCurve,
+
+    /// The point is the identity
+    Identity,
+
+    /// The point is not in the prime order subgroup
+    NotInSubgroup,
+}
+
+impl fmt::Display for PointValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InvalidEncoding => "invalid SEC1 point encoding",
+            Self::NotOnCurve => "point is not on the curve",
+            Self::Identity => "point is the identity",
+            Self::NotInSubgroup => "point is not in the prime order subgroup",
+        })
+    }
+}
+
+impl From<PointValidationError> for Error {
+    fn from(err: PointValidationError) -> Error {
+        #[cfg(feature = "std")]
+        {
+            Error::from_source(err)
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = err;
+            Error::new()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PointValidationError {}
+
+/// Outcome of each check performed by [`VerifyingKey::validate`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ValidationReport {
+    /// The point satisfies the curve equation
+    pub on_curve: bool,
+
+    /// The point is not the identity
+    pub not_identity: bool,
+
+    /// The point has the order of the curve's base point, i.e. it is in the
+    /// prime order subgroup
+    pub in_subgroup: bool,
+}
+
+impl ValidationReport {
+    /// Did the key pass every check?
+    pub fn is_valid(&self) -> bool {
+        self.error().is_none()
+    }
+
+    /// Get the first failed check, in the order they are listed in
+    /// ANSI X9.62, if any.
+    pub fn error(&self) -> Option<PointValidationError> {
+        if !self.not_identity {
+            Some(PointValidationError::Identity)
+        } else if !self.on_curve {
+            Some(PointValidationError::NotOnCurve)
+        } else if !self.in_subgroup {
+            Some(PointValidationError::NotInSubgroup)
+        } else {
+            None
+        }
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    ProjectivePoint<C>: CofactorGroup,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Initialize [`VerifyingKey`] from a SEC1-encoded public key, performing
+    /// the given level of validation.
+    ///
+    /// Unlike [`VerifyingKey::from_sec1_bytes`], the returned error
+    /// identifies which check failed, e.g. for audit logging.
+    pub fn from_sec1_bytes_validated(
+        bytes: &[u8],
+        validation: PublicKeyValidation,
+    ) -> Result<Self, PointValidationError> {
+        let encoded_point = EncodedPoint::<C>::from_bytes(bytes)
+            .map_err(|_| PointValidationError::InvalidEncoding)?;
+
+        if encoded_point.is_identity() {
+            return Err(PointValidationError::Identity);
+        }
+
+        let point = AffinePoint::<C>::from_encoded_point(&encoded_point)
+            .ok_or(PointValidationError::NotOnCurve)?;
+
+        if validation == PublicKeyValidation::Full
+            && !bool::from(ProjectivePoint::<C>::from(point).is_torsion_free())
+        {
+            return Err(PointValidationError::NotInSubgroup);
+        }
+
+        PublicKey::from_affine(point)
+            .map(|inner| Self { inner })
+            .map_err(|_| PointValidationError::Identity)
+    }
+
+    /// Perform full public key validation on this key, as described in
+    /// ANSI X9.62 and [NIST SP 800-56A Rev. 3] Section 5.6.2.3.3, reporting
+    /// the outcome of each check.
+    ///
+    /// Keys constructed by this crate are never the identity and always on
+    /// the curve, but all checks are performed again so the report can be
+    /// used by audit tooling without trusting how the key was imported.
+    ///
+    /// [NIST SP 800-56A Rev. 3]: https://csrc.nist.gov/publications/detail/sp/800-56a/rev-3/final
+    pub fn validate(&self) -> ValidationReport {
+        let point = ProjectivePoint::<C>::from(*self.as_affine());
+        let encoded_point = self.to_encoded_point(false);
+
+        ValidationReport {
+            on_curve: AffinePoint::<C>::from_encoded_point(&encoded_point).is_some(),
+            not_identity: !bool::from(point.is_identity()),
+            in_subgroup: bool::from(point.is_torsion_free()),
+        }
+    }
+}
@@ -1,21 +1,39 @@
 //! ECDSA verification key.
 
 use crate::{
-    hazmat::{DigestPrimitive, FromDigest, VerifyPrimitive},
-    Error, Result, Signature, SignatureSize,
+    hazmat::{
+        prehash_to_scalar, DigestPrimitive, EncodePrecomputedTables, FromDigest,
+        PrecomputeVerifyPrimitive, VerifyPrimitive,
+    },
+    Error, NormalizeLow, Result, Signature, SignatureSize,
+};
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    ops::Add,
+    str::FromStr,
 };
-use core::{cmp::Ordering, convert::TryFrom, fmt::Debug, ops::Add};
 use elliptic_curve::{
     consts::U1,
-    generic_array::ArrayLength,
+    generic_array::{typenum::Unsigned, ArrayLength, GenericArray},
+    group::{Curve as _, Group},
     sec1::{
-        EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize,
+        CompressedPointSize, EncodedPoint, FromEncodedPoint, ToEncodedPoint, UncompressedPointSize,
+        UntaggedPointSize,
     },
     weierstrass::{Curve, PointCompression},
-    AffinePoint, FieldSize, ProjectiveArithmetic, PublicKey, Scalar,
+    AffinePoint, FieldSize, ProjectiveArithmetic, ProjectivePoint, PublicKey, Scalar,
 };
 use signature::{digest::Digest, DigestVerifier};
 
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+#[cfg(feature = "der")]
+use crate::der;
+
 #[cfg(feature = "pkcs8")]
 use crate::elliptic_curve::{
     pkcs8::{self, FromPublicKey},
@@ -23,12 +41,37 @@ use crate::elliptic_curve::{
 };
 
 #[cfg(feature = "pem")]
-use core::str::FromStr;
+use crate::elliptic_curve::pkcs8::ToPublicKey;
+
+#[cfg(feature = "serde")]
+use {
+    core::marker::PhantomData,
+    serde::{de, ser, Deserialize, Serialize},
+};
 
 /// ECDSA verification key (i.e. public key). Generic over elliptic curves.
 ///
 /// Requires an [`elliptic_curve::ProjectiveArithmetic`] impl on the curve, and a
 /// [`VerifyPrimitive`] impl on its associated `AffinePoint` type.
+///
+/// Parsing signatures and verifying them against a [`VerifyingKey`] does not
+/// panic on malformed input within this crate, although curve-specific
+/// [`FromDigest`] and [`VerifyPrimitive`] impls must uphold the same.
+///
+/// ## SPKI and PEM
+///
+/// When the `pkcs8` feature is enabled, verifying keys can be decoded from
+/// X.509 `SubjectPublicKeyInfo` documents using the `FromPublicKey` trait,
+/// e.g. with `VerifyingKey::from_public_key_der`.
+///
+/// When the `pem` feature is enabled, `-----BEGIN PUBLIC KEY-----` documents
+/// can also be decoded using `VerifyingKey::from_public_key_pem`, and keys
+/// can be encoded as SPKI DER or PEM using the `ToPublicKey` trait's
+/// `to_public_key_der` and `to_public_key_pem` methods.
+///
+/// [`str::parse`] accepts hex of a SEC1-encoded public key, as produced by
+/// the [`Display`](fmt::Display) impl, regardless of which features are
+/// enabled.
 #[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
 #[derive(Clone, Debug)]
 pub struct VerifyingKey<C>
@@ -52,6 +95,31 @@ where
             .map_err(|_| Error::new())
     }
 
+    /// Initialize [`VerifyingKey`] from a SEC1-encoded public key, also
+    /// accepting the hybrid encoding described in ANSI X9.62 and emitted by
+    /// some legacy hardware.
+    ///
+    /// Hybrid encodings are uncompressed points tagged with `0x06` or `0x07`
+    /// to indicate whether the y-coordinate is even or odd, which must be
+    /// consistent with the encoded y-coordinate.
+    pub fn from_sec1_bytes_allow_hybrid(bytes: &[u8]) -> Result<Self> {
+        let is_y_odd = match bytes.first() {
+            Some(0x06) => false,
+            Some(0x07) => true,
+            _ => return Self::from_sec1_bytes(bytes),
+        };
+
+        let mut uncompressed = GenericArray::<u8, UncompressedPointSize<C>>::default();
+
+        if bytes.len() != uncompressed.len() || (bytes[bytes.len() - 1] & 1 == 1) != is_y_odd {
+            return Err(Error::new());
+        }
+
+        uncompressed[0] = 0x04;
+        uncompressed[1..].copy_from_slice(&bytes[1..]);
+        Self::from_sec1_bytes(&uncompressed)
+    }
+
     /// Initialize [`VerifyingKey`] from an [`EncodedPoint`].
     pub fn from_encoded_point(public_key: &EncodedPoint<C>) -> Result<Self> {
         PublicKey::<C>::from_encoded_point(public_key)
@@ -64,6 +132,239 @@ where
     pub fn to_encoded_point(&self, compress: bool) -> EncodedPoint<C> {
         self.inner.to_encoded_point(compress)
     }
+
+    /// Write the SEC1 encoding of this [`VerifyingKey`] into the start of
+    /// `buf`, optionally applying point compression, returning the number of
+    /// bytes written.
+    ///
+    /// Returns an error if `buf` is too short.
+    pub fn write_sec1_bytes(&self, compress: bool, buf: &mut [u8]) -> Result<usize> {
+        crate::write_into(self.to_encoded_point(compress).as_bytes(), buf)
+    }
+
+    /// Serialize this [`VerifyingKey`] as SEC1-encoded bytes, optionally
+    /// applying point compression.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_sec1_bytes(&self, compress: bool) -> Box<[u8]> {
+        self.to_encoded_point(compress).as_bytes().into()
+    }
+
+    /// Parse a [`VerifyingKey`] from hex of its (compressed or uncompressed)
+    /// SEC1 encoding. Both upper and lower case hex are accepted.
+    pub fn from_sec1_hex(hex: &str) -> Result<Self> {
+        let mut bytes = GenericArray::<u8, UncompressedPointSize<C>>::default();
+        let pairs = hex.as_bytes().chunks_exact(2);
+
+        if !pairs.remainder().is_empty() || pairs.len() > bytes.len() {
+            return Err(Error::new());
+        }
+
+        let len = pairs.len();
+
+        for (byte, pair) in bytes.iter_mut().zip(pairs) {
+            *byte = (hex_digit(pair[0])? << 4) | hex_digit(pair[1])?;
+        }
+
+        Self::from_sec1_bytes(&bytes[..len])
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    /// Initialize [`VerifyingKey`] from an affine point, e.g. one computed
+    /// directly by a protocol rather than parsed from an encoding.
+    ///
+    /// Returns an error if the point is the identity.
+    pub fn from_affine(point: AffinePoint<C>) -> Result<Self> {
+        PublicKey::from_affine(point)
+            .map(|inner| Self { inner })
+            .map_err(|_| Error::new())
+    }
+
+    /// Borrow the public point of this [`VerifyingKey`] as an affine point.
+    pub fn as_affine(&self) -> &AffinePoint<C> {
+        self.inner.as_affine()
+    }
+
+    /// Compute the [`VerifyingKey`] for the public point `Q + tweak * G`,
+    /// which corresponds to the output of [`SigningKey::tweak_add`] on the
+    /// associated signing key.
+    ///
+    /// Returns an error if the resulting point is the identity.
+    ///
+    /// [`SigningKey::tweak_add`]: crate::SigningKey::tweak_add
+    pub fn tweak_add(&self, tweak: &Scalar<C>) -> Result<Self> {
+        let point = self.inner.to_projective() + ProjectivePoint::<C>::generator() * tweak;
+
+        PublicKey::from_affine(point.to_affine())
+            .map(|inner| Self { inner })
+            .map_err(|_| Error::new())
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Verify the signature against the given message using the digest
+    /// algorithm `D` rather than the curve's default, e.g.
+    /// `verifying_key.verify_with::<Sha384>(msg, &signature)`.
+    pub fn verify_with<D>(&self, msg: &[u8], signature: &Signature<C>) -> Result<()>
+    where
+        D: Digest,
+    {
+        self.verify_digest(D::new().chain(msg), signature)
+    }
+
+    /// Verify an ASN.1 DER-encoded signature against the given message,
+    /// hashed using the curve's default digest algorithm.
+    #[cfg(feature = "der")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "der")))]
+    pub fn verify_der(&self, msg: &[u8], signature: &[u8]) -> Result<()>
+    where
+        C: DigestPrimitive,
+        der::MaxSize<C>: ArrayLength<u8>,
+        <FieldSize<C> as Add>::Output: Add<der::MaxOverhead> + ArrayLength<u8>,
+    {
+        let signature = self.traced(Signature::from_der(signature), "der", "encoding")?;
+        self.verify_digest_encoded(C::Digest::new().chain(msg), &signature, "der")
+    }
+
+    /// Verify the signature against the given message prehash, e.g. a digest
+    /// computed by another party, as produced by [`SigningKey::try_sign_prehash`].
+    ///
+    /// The prehash is converted to a scalar as described by `bits2int` in
+    /// RFC 6979. Returns an error if it is shorter than half the field size.
+    ///
+    /// [`SigningKey::try_sign_prehash`]: crate::SigningKey::try_sign_prehash
+    pub fn verify_prehash(&self, prehash: &[u8], signature: &Signature<C>) -> Result<()> {
+        let msg_scalar = self.traced(prehash_to_scalar::<C>(prehash), "prehash", "encoding")?;
+        let result = self
+            .inner
+            .as_affine()
+            .verify_prehashed(&msg_scalar, signature);
+        self.traced(result, "prehash", "equation")
+    }
+
+    /// Verify the signature against the given message digest, logging a
+    /// failure as one of a signature supplied in the given encoding.
+    #[deny(clippy::indexing_slicing, clippy::panic)]
+    fn verify_digest_encoded<D>(
+        &self,
+        digest: D,
+        signature: &Signature<C>,
+        encoding: &'static str,
+    ) -> Result<()>
+    where
+        D: Digest,
+    {
+        let result = self
+            .inner
+            .as_affine()
+            .verify_prehashed(&Scalar::<C>::from_digest(digest), signature);
+
+        self.traced(result, encoding, "equation")
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C> + NormalizeLow,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Verify the signature against the given message digest, rejecting
+    /// signatures which are not in "low S" form.
+    ///
+    /// This matches the strict verification rules used by e.g. Bitcoin
+    /// consensus, where only the normalized signature (see
+    /// [`Signature::normalize_s`]) of a given message is accepted.
+    pub fn verify_digest_strict<D>(&self, digest: D, signature: &Signature<C>) -> Result<()>
+    where
+        D: Digest,
+    {
+        let (_, is_high_s) = signature.s().normalize_low();
+
+        if is_high_s {
+            return self.traced(Err(Error::new()), "fixed", "high-s");
+        }
+
+        self.verify_digest(digest, signature)
+    }
+
+    /// Verify the signature against the given message, rejecting signatures
+    /// which are not in "low S" form.
+    ///
+    /// See [`VerifyingKey::verify_digest_strict`] for more information.
+    pub fn verify_strict(&self, msg: &[u8], signature: &Signature<C>) -> Result<()>
+    where
+        C: DigestPrimitive,
+    {
+        self.verify_digest_strict(C::Digest::new().chain(msg), signature)
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + DigestPrimitive,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Start verifying a message which is supplied incrementally, hashing it
+    /// with the curve's default digest algorithm.
+    pub fn verify_context(&self) -> VerifyContext<'_, C, C::Digest> {
+        VerifyContext::new(self)
+    }
+}
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Log a failed verification when the `tracing` feature is enabled,
+    /// identifying this key by its fingerprint, before returning `result`.
+    ///
+    /// `encoding` is that of the signature (`fixed`, `der` or `prehash`),
+    /// and `category` the reason it was rejected: `encoding` if it could not
+    /// be decoded, `high-s` if it is not in "low S" form, or `equation` if
+    /// it does not satisfy the verification equation.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn traced<T>(
+        &self,
+        result: Result<T>,
+        encoding: &'static str,
+        category: &'static str,
+    ) -> Result<T> {
+        #[cfg(feature = "tracing")]
+        if result.is_err() {
+            tracing::debug!(
+                curve = ?C::default(),
+                fingerprint = %self.fingerprint(),
+                encoding,
+                category,
+                "ECDSA signature verification failed"
+            );
+        }
+
+        result
+    }
 }
 
 impl<C> Copy for VerifyingKey<C> where C: Curve + ProjectiveArithmetic {}
@@ -71,25 +372,27 @@ impl<C> Copy for VerifyingKey<C> where C: Curve + ProjectiveArithmetic {}
 impl<C, D> DigestVerifier<D, Signature<C>> for VerifyingKey<C>
 where
     C: Curve + ProjectiveArithmetic,
-    D: Digest<OutputSize = FieldSize<C>>,
-    AffinePoint<C>: VerifyPrimitive<C>,
+    D: Digest,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
     Scalar<C>: FromDigest<C>,
     SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
 {
+    #[deny(clippy::indexing_slicing, clippy::panic)]
     fn verify_digest(&self, digest: D, signature: &Signature<C>) -> Result<()> {
-        self.inner
-            .as_affine()
-            .verify_prehashed(&Scalar::<C>::from_digest(digest), signature)
+        self.verify_digest_encoded(digest, signature, "fixed")
     }
 }
 
 impl<C> signature::Verifier<Signature<C>> for VerifyingKey<C>
 where
     C: Curve + ProjectiveArithmetic + DigestPrimitive,
-    C::Digest: Digest<OutputSize = FieldSize<C>>,
-    AffinePoint<C>: VerifyPrimitive<C>,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
     Scalar<C>: FromDigest<C>,
     SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
 {
     fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
         self.verify_digest(C::Digest::new().chain(msg), signature)
@@ -173,7 +476,7 @@ where
     UncompressedPointSize<C>: ArrayLength<u8>,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.inner.partial_cmp(&other.inner)
+        Some(self.cmp(other))
     }
 }
 
@@ -185,7 +488,23 @@ where
     UncompressedPointSize<C>: ArrayLength<u8>,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.inner.cmp(&other.inner)
+        // Order by the compressed encoding, as is conventional for sorting
+        // keys in multi-party protocols
+        self.to_encoded_point(true)
+            .as_bytes()
+            .cmp(other.to_encoded_point(true).as_bytes())
+    }
+}
+
+impl<C> Hash for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_encoded_point(true).as_bytes().hash(state)
     }
 }
 
@@ -207,7 +526,7 @@ where
 #[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
 impl<C> FromPublicKey for VerifyingKey<C>
 where
-    C: Curve + AlgorithmParameters + ProjectiveArithmetic + PointCompression,
+    C: Curve + AlgorithmParameters + ProjectiveArithmetic,
     AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
     UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
     UncompressedPointSize<C>: ArrayLength<u8>,
@@ -217,18 +536,833 @@ where
     }
 }
 
+#[cfg(feature = "pkcs8")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pkcs8")))]
+impl<C> TryFrom<pkcs8::SubjectPublicKeyInfo<'_>> for VerifyingKey<C>
+where
+    C: Curve + AlgorithmParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    type Error = pkcs8::Error;
+
+    fn try_from(spki: pkcs8::SubjectPublicKeyInfo<'_>) -> pkcs8::Result<Self> {
+        Self::from_spki(spki)
+    }
+}
+
 #[cfg(feature = "pem")]
 #[cfg_attr(docsrs, doc(cfg(feature = "pem")))]
+impl<C> ToPublicKey for VerifyingKey<C>
+where
+    C: Curve + AlgorithmParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn to_public_key_der(&self) -> pkcs8::Result<pkcs8::PublicKeyDocument> {
+        self.inner.to_public_key_der()
+    }
+}
+
 impl<C> FromStr for VerifyingKey<C>
 where
-    C: Curve + AlgorithmParameters + ProjectiveArithmetic + PointCompression,
+    C: Curve + ProjectiveArithmetic,
     AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
     UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
     UncompressedPointSize<C>: ArrayLength<u8>,
 {
     type Err = Error;
 
+    /// Parse hex of a SEC1-encoded public key as produced by the
+    /// [`Display`] impl.
+    ///
+    /// [`Display`]: fmt::Display
     fn from_str(s: &str) -> Result<Self> {
-        Self::from_public_key_pem(s).map_err(|_| Error::new())
+        Self::from_sec1_hex(s)
+    }
+}
+
+/// Displays the compressed SEC1 encoding of the key as lower case hex.
+impl<C> fmt::Display for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        HexDisplay(self.to_encoded_point(true).as_bytes()).fmt(f)
+    }
+}
+
+/// Serializes the compressed SEC1 encoding of the key: as lower case hex for
+/// human-readable formats, and as bytes otherwise.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<C> Serialize for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn serialize<S: ser::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        let encoded_point = self.to_encoded_point(true);
+
+        if serializer.is_human_readable() {
+            serializer.collect_str(&HexDisplay(encoded_point.as_bytes()))
+        } else {
+            serializer.serialize_bytes(encoded_point.as_bytes())
+        }
+    }
+}
+
+/// Accepts both compressed and uncompressed SEC1 encodings, in the same
+/// representation used by the [`Serialize`] impl.
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, C> Deserialize<'de> for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn deserialize<D: de::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        struct Sec1Visitor<C>(PhantomData<C>);
+
+        impl<'de, C> de::Visitor<'de> for Sec1Visitor<C>
+        where
+            C: Curve + ProjectiveArithmetic,
+            AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+            UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+            UncompressedPointSize<C>: ArrayLength<u8>,
+        {
+            type Value = VerifyingKey<C>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("SEC1-encoded public key")
+            }
+
+            fn visit_bytes<E: de::Error>(
+                self,
+                bytes: &[u8],
+            ) -> core::result::Result<Self::Value, E> {
+                VerifyingKey::from_sec1_bytes(bytes)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(bytes), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, hex: &str) -> core::result::Result<Self::Value, E> {
+                VerifyingKey::from_sec1_hex(hex)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(hex), &self))
+            }
+        }
+
+        let visitor = Sec1Visitor(PhantomData);
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(visitor)
+        } else {
+            deserializer.deserialize_bytes(visitor)
+        }
+    }
+}
+
+/// Displays bytes as lower case hex.
+struct HexDisplay<'a>(&'a [u8]);
+
+impl fmt::Display for HexDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a single hex digit
+fn hex_digit(digit: u8) -> Result<u8> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(Error::new()),
+    }
+}
+
+/// Incremental verification of a message which is supplied in chunks, e.g.
+/// when verifying a signature over a large file without buffering it.
+///
+/// With the `std` feature enabled this also implements [`std::io::Write`],
+/// so e.g. [`std::io::copy`] can be used to feed it a file.
+///
+/// Services which accept messages from untrusted clients can limit their
+/// size with [`VerifyContext::with_max_len`], and report how much of the
+/// message has been hashed with [`VerifyContext::with_progress`].
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+#[derive(Clone)]
+pub struct VerifyContext<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+{
+    /// Key the signature is verified against
+    verifying_key: &'a VerifyingKey<C>,
+
+    /// Digest of the message so far
+    digest: D,
+
+    /// Length of the message so far, including any chunk which was rejected
+    /// for exceeding `max_len`
+    len: u64,
+
+    /// Maximum length of the message
+    max_len: Option<u64>,
+
+    /// Callback which is passed the length hashed so far after each chunk
+    progress: Option<&'a dyn Fn(u64)>,
+}
+
+impl<'a, C, D> VerifyContext<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Start verifying a message against `verifying_key`, hashing it with
+    /// the digest algorithm `D`.
+    pub fn new(verifying_key: &'a VerifyingKey<C>) -> Self {
+        Self {
+            verifying_key,
+            digest: D::new(),
+            len: 0,
+            max_len: None,
+            progress: None,
+        }
+    }
+
+    /// Reject messages longer than `max_len` bytes.
+    pub fn with_max_len(mut self, max_len: u64) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Call `progress` with the number of bytes hashed so far after each
+    /// chunk of the message.
+    pub fn with_progress(mut self, progress: &'a dyn Fn(u64)) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Hash the next chunk of the message.
+    ///
+    /// Returns an error, without hashing the chunk, if it would make the
+    /// message longer than the maximum length. Verification then fails
+    /// whether or not more chunks are supplied.
+    pub fn update(&mut self, chunk: impl AsRef<[u8]>) -> Result<()> {
+        let chunk = chunk.as_ref();
+        let chunk_len = u64::try_from(chunk.len()).unwrap_or(u64::MAX);
+        self.len = self.len.saturating_add(chunk_len);
+
+        if self.is_too_long() {
+            return Err(Error::new());
+        }
+
+        self.digest.update(chunk);
+
+        if let Some(progress) = self.progress {
+            progress(self.len);
+        }
+
+        Ok(())
+    }
+
+    /// Get the length of the message hashed so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Is the message hashed so far empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Verify the signature against the message hashed so far, consuming the
+    /// context.
+    ///
+    /// Returns an error if any chunk was rejected by [`VerifyContext::update`].
+    pub fn verify(self, signature: &Signature<C>) -> Result<()> {
+        if self.is_too_long() {
+            return Err(Error::new());
+        }
+
+        self.verifying_key.verify_digest(self.digest, signature)
+    }
+
+    /// Has the message exceeded the maximum length?
+    fn is_too_long(&self) -> bool {
+        matches!(self.max_len, Some(max_len) if self.len > max_len)
+    }
+}
+
+impl<'a, C, D> Debug for VerifyContext<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    VerifyingKey<C>: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifyContext")
+            .field("verifying_key", self.verifying_key)
+            .field("len", &self.len)
+            .field("max_len", &self.max_len)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<'a, C, D> std::io::Write for VerifyContext<'a, C, D>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Version of the [`PrecomputedVerifyingKey`] serialization format
+const PRECOMPUTED_KEY_VERSION: u8 = 1;
+
+/// [`VerifyingKey`] bundled with tables precomputed from its public point,
+/// which speed up repeated verification with the same key (see
+/// [`PrecomputeVerifyPrimitive`]).
+///
+/// This is intended for hot keys, e.g. a certificate authority key used to
+/// verify many certificates, where the cost of computing the tables is
+/// amortized over many verifications.
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+pub struct PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    verifying_key: VerifyingKey<C>,
+    tables: <AffinePoint<C> as PrecomputeVerifyPrimitive<C>>::Tables,
+}
+
+impl<C> PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    /// Compute the tables for the given [`VerifyingKey`].
+    pub fn new(verifying_key: VerifyingKey<C>) -> Self {
+        let tables = verifying_key.inner.as_affine().precompute();
+
+        Self {
+            verifying_key,
+            tables,
+        }
+    }
+
+    /// Get the [`VerifyingKey`] the tables were computed for.
+    pub fn verifying_key(&self) -> &VerifyingKey<C> {
+        &self.verifying_key
+    }
+}
+
+impl<C> PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: EncodePrecomputedTables<C> + FromEncodedPoint<C> + ToEncodedPoint<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a key and its tables serialized with
+    /// [`PrecomputedVerifyingKey::to_bytes`].
+    ///
+    /// Tables which were not computed for the key are rejected by
+    /// [`EncodePrecomputedTables::decode_tables`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key_len = UncompressedPointSize::<C>::USIZE;
+
+        if bytes.len() < 2 + key_len
+            || bytes[0] != PRECOMPUTED_KEY_VERSION
+            || bytes[1] != AffinePoint::<C>::TABLES_VERSION
+        {
+            return Err(Error::new());
+        }
+
+        let encoded_point =
+            EncodedPoint::<C>::from_bytes(&bytes[2..][..key_len]).map_err(|_| Error::new())?;
+
+        if encoded_point.is_compressed() {
+            return Err(Error::new());
+        }
+
+        let verifying_key = VerifyingKey::from_encoded_point(&encoded_point)?;
+        let tables = verifying_key
+            .inner
+            .as_affine()
+            .decode_tables(&bytes[2 + key_len..])?;
+
+        Ok(Self {
+            verifying_key,
+            tables,
+        })
+    }
+
+    /// Serialize this key and its tables.
+    ///
+    /// The encoding is a version byte for the encoding as a whole, followed
+    /// by [`EncodePrecomputedTables::TABLES_VERSION`], the uncompressed SEC1
+    /// encoding of the key, and the encoded tables.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::from([PRECOMPUTED_KEY_VERSION, AffinePoint::<C>::TABLES_VERSION]);
+        bytes.extend_from_slice(self.verifying_key.to_encoded_point(false).as_bytes());
+        bytes.extend_from_slice(&AffinePoint::<C>::encode_tables(&self.tables));
+        bytes
+    }
+}
+
+impl<C> From<VerifyingKey<C>> for PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn from(verifying_key: VerifyingKey<C>) -> Self {
+        Self::new(verifying_key)
+    }
+}
+
+impl<C> Debug for PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrecomputedVerifyingKey")
+            .field("verifying_key", &self.verifying_key)
+            .finish()
+    }
+}
+
+impl<C, D> DigestVerifier<D, Signature<C>> for PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C> + ToEncodedPoint<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify_digest(&self, digest: D, signature: &Signature<C>) -> Result<()> {
+        let result = self
+            .verifying_key
+            .inner
+            .as_affine()
+            .verify_prehashed_precomputed(
+                &self.tables,
+                &Scalar::<C>::from_digest(digest),
+                signature,
+            );
+
+        self.verifying_key.traced(result, "fixed", "equation")
+    }
+}
+
+impl<C> signature::Verifier<Signature<C>> for PrecomputedVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + DigestPrimitive,
+    AffinePoint<C>: PrecomputeVerifyPrimitive<C> + ToEncodedPoint<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
+        self.verify_digest(C::Digest::new().chain(msg), signature)
+    }
+}
+
+/// [`VerifyingKey`] which is guaranteed to have been parsed from, and to
+/// serialize to, the compressed SEC1 encoding of its public point.
+///
+/// Any other encoding of the same point (uncompressed, hybrid, or a
+/// compressed encoding which does not round-trip) is rejected, so the
+/// serialized key bytes can be used as an identity, e.g. in consensus
+/// protocols.
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+#[derive(Clone, Debug)]
+pub struct CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    inner: VerifyingKey<C>,
+}
+
+impl<C> CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`CanonicalVerifyingKey`] from a compressed SEC1-encoded
+    /// public key, rejecting all other encodings.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        let encoded_point = EncodedPoint::<C>::from_bytes(bytes).map_err(|_| Error::new())?;
+
+        if !encoded_point.is_compressed() {
+            return Err(Error::new());
+        }
+
+        let inner = VerifyingKey::from_encoded_point(&encoded_point)?;
+
+        if inner.to_encoded_point(true).as_bytes() != bytes {
+            return Err(Error::new());
+        }
+
+        Ok(Self { inner })
+    }
+
+    /// Serialize this key as a compressed SEC1 [`EncodedPoint`].
+    pub fn to_encoded_point(&self) -> EncodedPoint<C> {
+        self.inner.to_encoded_point(true)
+    }
+
+    /// Borrow the inner [`VerifyingKey`].
+    pub fn as_verifying_key(&self) -> &VerifyingKey<C> {
+        &self.inner
+    }
+}
+
+impl<C> AsRef<VerifyingKey<C>> for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    fn as_ref(&self) -> &VerifyingKey<C> {
+        &self.inner
+    }
+}
+
+impl<C> Copy for CanonicalVerifyingKey<C> where C: Curve + ProjectiveArithmetic {}
+
+impl<C, D> DigestVerifier<D, Signature<C>> for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify_digest(&self, digest: D, signature: &Signature<C>) -> Result<()> {
+        self.inner.verify_digest(digest, signature)
+    }
+}
+
+impl<C> signature::Verifier<Signature<C>> for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + DigestPrimitive,
+    AffinePoint<C>: ToEncodedPoint<C> + VerifyPrimitive<C>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
+        signature::Verifier::verify(&self.inner, msg, signature)
+    }
+}
+
+impl<C> From<CanonicalVerifyingKey<C>> for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    fn from(canonical_key: CanonicalVerifyingKey<C>) -> VerifyingKey<C> {
+        canonical_key.inner
+    }
+}
+
+impl<C> From<VerifyingKey<C>> for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+{
+    fn from(verifying_key: VerifyingKey<C>) -> CanonicalVerifyingKey<C> {
+        CanonicalVerifyingKey {
+            inner: verifying_key,
+        }
+    }
+}
+
+impl<C> Eq for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+}
+
+impl<C> PartialEq for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.eq(&other.inner)
+    }
+}
+
+impl<C> TryFrom<&[u8]> for CanonicalVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_sec1_bytes(bytes)
+    }
+}
+
+/// [`VerifyingKey`] stored as a compressed SEC1 point, for applications
+/// which hold many keys in memory.
+///
+/// The point is validated when the key is constructed, and decompressed
+/// each time a signature is verified. Holding a [`CompactVerifyingKey`]
+/// therefore trades a field square root per verification for storing only
+/// the compressed point (e.g. 33 bytes for NIST P-256) instead of a decoded
+/// [`AffinePoint`].
+///
+/// To verify many signatures with the same key, convert it to a
+/// [`VerifyingKey`] with [`CompactVerifyingKey::to_verifying_key`] first.
+#[cfg_attr(docsrs, doc(cfg(feature = "verify")))]
+#[derive(Clone)]
+pub struct CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+    bytes: GenericArray<u8, CompressedPointSize<C>>,
+}
+
+impl<C> CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse a [`CompactVerifyingKey`] from any SEC1-encoded public key
+    /// accepted by [`VerifyingKey::from_sec1_bytes`].
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self> {
+        VerifyingKey::from_sec1_bytes(bytes).map(|key| Self::from(&key))
+    }
+
+    /// Borrow the compressed SEC1 encoding of this key.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Decompress this key into a [`VerifyingKey`].
+    ///
+    /// The point was validated when this key was constructed, so this only
+    /// fails if a curve implementation decompresses points inconsistently.
+    pub fn to_verifying_key(&self) -> Result<VerifyingKey<C>> {
+        VerifyingKey::from_sec1_bytes(&self.bytes)
+    }
+}
+
+impl<C> AsRef<[u8]> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<C> Debug for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CompactVerifyingKey")
+            .field(&format_args!("{}", HexDisplay(&self.bytes)))
+            .finish()
+    }
+}
+
+impl<C, D> DigestVerifier<D, Signature<C>> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    D: Digest,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify_digest(&self, digest: D, signature: &Signature<C>) -> Result<()> {
+        self.to_verifying_key()?.verify_digest(digest, signature)
+    }
+}
+
+impl<C> signature::Verifier<Signature<C>> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic + DigestPrimitive,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C> + VerifyPrimitive<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    Scalar<C>: FromDigest<C>,
+    SignatureSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
+        signature::Verifier::verify(&self.to_verifying_key()?, msg, signature)
+    }
+}
+
+impl<C> From<&VerifyingKey<C>> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn from(verifying_key: &VerifyingKey<C>) -> CompactVerifyingKey<C> {
+        // Verifying keys are never the identity, so always have a compressed
+        // encoding of exactly this size
+        CompactVerifyingKey {
+            bytes: GenericArray::clone_from_slice(verifying_key.to_encoded_point(true).as_bytes()),
+        }
+    }
+}
+
+impl<C> From<VerifyingKey<C>> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn from(verifying_key: VerifyingKey<C>) -> CompactVerifyingKey<C> {
+        CompactVerifyingKey::from(&verifying_key)
+    }
+}
+
+impl<C> TryFrom<&CompactVerifyingKey<C>> for VerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(compact_key: &CompactVerifyingKey<C>) -> Result<VerifyingKey<C>> {
+        compact_key.to_verifying_key()
+    }
+}
+
+impl<C> Eq for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+}
+
+impl<C> PartialEq for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes == other.bytes
+    }
+}
+
+impl<C> Hash for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state)
+    }
+}
+
+impl<C> TryFrom<&[u8]> for CompactVerifyingKey<C>
+where
+    C: Curve + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldSize<C>: Add<U1>,
+    CompressedPointSize<C>: ArrayLength<u8>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        Self::from_sec1_bytes(bytes)
     }
 }
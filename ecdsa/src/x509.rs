@@ -0,0 +1,126 @@
+//! Support for extracting [`VerifyingKey`]s from X.509 certificates
+//! ([RFC 5280]).
+//!
+//! Only the `subjectPublicKeyInfo` of the certificate is decoded: the
+//! certificate's own signature, validity period, and extensions are not
+//! checked, and it is up to the caller to establish that the certificate is
+//! trusted before using the key.
+//!
+//! [RFC 5280]: https://tools.ietf.org/html/rfc5280#section-4.1
+
+use crate::{Error, Result, VerifyingKey};
+use core::ops::Add;
+use der::{Decoder, TagNumber};
+use elliptic_curve::{
+    consts::U1,
+    generic_array::ArrayLength,
+    pkcs8::{FromPublicKey, SubjectPublicKeyInfo},
+    sec1::{FromEncodedPoint, ToEncodedPoint, UncompressedPointSize, UntaggedPointSize},
+    weierstrass::Curve,
+    AffinePoint, AlgorithmParameters, ProjectiveArithmetic,
+};
+
+/// Context-specific tag of the `version` field of a `TBSCertificate`
+const VERSION_TAG: TagNumber = TagNumber::new(0);
+
+impl<C> VerifyingKey<C>
+where
+    C: Curve + AlgorithmParameters + ProjectiveArithmetic,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    UntaggedPointSize<C>: Add<U1> + ArrayLength<u8>,
+    UncompressedPointSize<C>: ArrayLength<u8>,
+{
+    /// Parse the subject public key of an ASN.1 DER-encoded X.509
+    /// certificate.
+    ///
+    /// Returns an error if the certificate is malformed, or if the key is not
+    /// an elliptic curve key on the curve `C`.
+    pub fn from_certificate_der(bytes: &[u8]) -> Result<Self> {
+        let mut decoder = Decoder::new(bytes);
+
+        let spki = decoder
+            .sequence(|certificate| {
+                let spki = certificate.sequence(decode_tbs_certificate)?;
+
+                // signatureAlgorithm, signatureValue
+                certificate.any()?;
+                certificate.any()?;
+                Ok(spki)
+            })
+            .and_then(|spki| decoder.finish(spki))
+            .map_err(|_| Error::new())?;
+
+        Self::from_spki(spki).map_err(|_| Error::new())
+    }
+}
+
+/// Decode the `subjectPublicKeyInfo` of a `TBSCertificate`, skipping the
+/// rest of its fields.
+fn decode_tbs_certificate<'a>(
+    tbs_certificate: &mut Decoder<'a>,
+) -> der::Result<SubjectPublicKeyInfo<'a>> {
+    tbs_certificate.context_specific(VERSION_TAG)?;
+
+    // serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        tbs_certificate.any()?;
+    }
+
+    let spki = tbs_certificate.decode()?;
+
+    // issuerUniqueID, subjectUniqueID, extensions
+    while !tbs_certificate.is_finished() {
+        tbs_certificate.any()?;
+    }
+
+    Ok(spki)
+}
+
+#[cfg(all(test, feature = "dev"))]
+mod tests {
+    use crate::VerifyingKey;
+    use elliptic_curve::dev::MockCurve;
+    use hex_literal::hex;
+
+    /// Self-signed certificate for the public key from RFC 6979 Appendix
+    /// A.2.5 (P-256), with a basic constraints extension
+    const CERTIFICATE: [u8; 297] = hex!(
+        "308201253081cca003020102020101300a06082a8648ce3d0403023012311030"
+        "0e06035504030c076578616d706c65301e170d3231303130313030303030305a"
+        "170d3331303130313030303030305a30123110300e06035504030c076578616d"
+        "706c653059301306072a8648ce3d020106082a8648ce3d0301070342000460fe"
+        "d4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903"
+        "fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299a313"
+        "3011300f0603551d130101ff040530030101ff300a06082a8648ce3d04030203"
+        "48003045022100a174c7a72e6d57e0355b6e790647db23741e86dccf970b0169"
+        "a0c4655d12919c0220644584f291e24edf445cf0366ff8d15ea3db575904bead"
+        "f14fa6f2efaa258ec7"
+    );
+
+    #[test]
+    fn subject_public_key() {
+        let key = VerifyingKey::<MockCurve>::from_certificate_der(&CERTIFICATE).unwrap();
+
+        assert_eq!(
+            key.to_encoded_point(false).as_bytes(),
+            &hex!(
+                "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+                "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+            )[..]
+        );
+    }
+
+    #[test]
+    fn reject_malformed_certificates() {
+        assert!(VerifyingKey::<MockCurve>::from_certificate_der(&CERTIFICATE[..296]).is_err());
+        assert!(VerifyingKey::<MockCurve>::from_certificate_der(
+            &[&CERTIFICATE[..], &[0]].concat()
+        )
+        .is_err());
+
+        // Subject public key with a different curve OID
+        let mut other_curve = CERTIFICATE;
+        other_curve[121] ^= 1;
+        assert!(VerifyingKey::<MockCurve>::from_certificate_der(&other_curve).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! Checks that operations on fixed-size encodings, and signing and verifying
+//! prehashed messages, don't heap allocate
+
+#![cfg(all(feature = "dev", feature = "std"))]
+
+use core::convert::TryFrom;
+use elliptic_curve::{
+    dev::{MockCurve, Scalar},
+    group::ff::PrimeField,
+};
+use hex_literal::hex;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+type Signature = ecdsa::Signature<MockCurve>;
+
+/// Signature from RFC 6979 Appendix 2.5 (NIST P-256 + SHA-256, "sample")
+const EXAMPLE_SIGNATURE: [u8; 64] = hex!(
+    "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"
+    "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+);
+
+/// Public key from RFC 6979 Appendix 2.5 (NIST P-256)
+#[cfg(feature = "verify")]
+const EXAMPLE_PUBLIC_KEY: [u8; 65] = hex!(
+    "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"
+    "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+);
+
+/// Global allocator which counts the number of allocations performed
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Assert that the given function doesn't perform any heap allocations
+fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let result = f();
+    assert_eq!(
+        ALLOCATIONS.load(Ordering::SeqCst),
+        before,
+        "heap allocation"
+    );
+    result
+}
+
+// NOTE: these checks are all performed in a single test, since the test
+// harness running other tests concurrently would skew the allocation count.
+#[test]
+fn fixed_encodings_do_not_allocate() {
+    use ecdsa::hazmat::FromDigest;
+    use sha2::{Digest, Sha256};
+
+    let signature = assert_no_alloc(|| {
+        Signature::validate_bytes(&EXAMPLE_SIGNATURE).unwrap();
+        Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap()
+    });
+
+    assert_no_alloc(|| {
+        let mut buf = [0u8; 80];
+        assert_eq!(signature.write_bytes(&mut buf).unwrap(), 64);
+        assert_eq!(&buf[..64], &EXAMPLE_SIGNATURE[..]);
+        assert!(signature.write_bytes(&mut buf[..63]).is_err());
+    });
+
+    assert_no_alloc(|| {
+        let (r, s) = signature.split_scalars();
+        Signature::from_scalars(r.to_repr(), s.to_repr()).unwrap();
+        Scalar::from_digest(Sha256::new().chain("sample"));
+    });
+
+    #[cfg(feature = "der")]
+    assert_no_alloc(|| {
+        let der = signature.to_der();
+        ecdsa::DerSignatureRef::<MockCurve>::try_from(der.as_bytes()).unwrap();
+        assert_eq!(Signature::from_der(der.as_bytes()).unwrap(), signature);
+
+        let mut buf = [0u8; 80];
+        let len = signature.write_der(&mut buf).unwrap();
+        assert_eq!(&buf[..len], der.as_bytes());
+        assert!(signature.write_der(&mut buf[..len - 1]).is_err());
+    });
+
+    assert_no_alloc(|| {
+        use ecdsa::{
+            dev::test_curve::{self, AffinePoint, ProjectivePoint},
+            hazmat::{SignPrimitive, VerifyPrimitive},
+        };
+        use elliptic_curve::group::Group;
+
+        let secret_scalar = test_curve::Scalar::from(0x0123_4567_89ab_cdef);
+        let public_key = AffinePoint::from(ProjectivePoint::generator() * secret_scalar);
+        let hashed_msg = test_curve::Scalar::from_digest(Sha256::new().chain("sample"));
+        let ephemeral_scalar = test_curve::Scalar::from(0x0fed_cba9_8765_4321);
+
+        let signature = secret_scalar
+            .try_sign_prehashed(&ephemeral_scalar, &hashed_msg)
+            .unwrap();
+
+        public_key
+            .verify_prehashed(&hashed_msg, &signature)
+            .unwrap();
+    });
+
+    #[cfg(feature = "verify")]
+    assert_no_alloc(|| {
+        let verifying_key =
+            ecdsa::VerifyingKey::<MockCurve>::from_sec1_bytes(&EXAMPLE_PUBLIC_KEY).unwrap();
+        verifying_key.to_encoded_point(false);
+
+        let mut buf = [0u8; 80];
+        assert_eq!(verifying_key.write_sec1_bytes(false, &mut buf).unwrap(), 65);
+        assert_eq!(&buf[..65], &EXAMPLE_PUBLIC_KEY[..]);
+        assert!(verifying_key
+            .write_sec1_bytes(false, &mut buf[..64])
+            .is_err());
+    });
+}
@@ -0,0 +1,118 @@
+//! Interoperability with OpenSSL's deterministic ECDSA signing (RFC 6979),
+//! using the fixtures in `openssl/p256.txt` (see `openssl/generate.sh`).
+//!
+//! `MockCurve` has no arithmetic, so rather than signing, these tests check
+//! that each OpenSSL signature `(r, s)` satisfies 𝑠𝑘 = 𝑧 + 𝑟𝑑 for the
+//! ephemeral scalar `k` and message scalar `z` derived by this crate. This
+//! covers `bits2int` for digests both shorter and longer than the field, and
+//! OpenSSL does not normalize `s`, so a mismatch in either is detected.
+
+#![cfg(all(feature = "der", feature = "dev", feature = "sign"))]
+
+use core::convert::TryFrom;
+use ecdsa::{
+    elliptic_curve::{
+        bigint::{ArrayEncoding, Limb, U256},
+        dev::{MockCurve, Scalar},
+        group::ff::PrimeField,
+        Curve, NonZeroScalar,
+    },
+    hazmat::FromDigest,
+    nonce::{NonceGenerator, Rfc6979},
+    Signature,
+};
+use sha2::{
+    digest::{BlockInput, Digest, FixedOutput, Reset, Update},
+    Sha224, Sha256, Sha384, Sha512,
+};
+
+/// OpenSSL 3 fixtures: `digest:d:message:signature`, hex encoded
+const FIXTURES: &str = include_str!("openssl/p256.txt");
+
+/// Decode a hex string
+fn decode_hex(hex: &str) -> Vec<u8> {
+    assert_eq!(hex.len() % 2, 0, "odd-length hex: {}", hex);
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..(i + 2)], 16).expect("invalid hex"))
+        .collect()
+}
+
+/// Convert a scalar to an integer
+fn to_uint(scalar: &Scalar) -> U256 {
+    U256::from_be_slice(&scalar.to_repr())
+}
+
+/// Compute `a + b` modulo the order, for `a, b` less than the order.
+fn add_mod(a: &U256, b: &U256) -> U256 {
+    let (sum, carry) = a.adc(b, Limb::ZERO);
+    let (reduced, borrow) = sum.sbb(&MockCurve::ORDER, Limb::ZERO);
+
+    if carry != Limb::ZERO || borrow == Limb::ZERO {
+        reduced
+    } else {
+        sum
+    }
+}
+
+/// Compute `a * b` modulo the order, for `a` less than the order.
+fn mul_mod(a: &U256, b: &U256) -> U256 {
+    b.to_be_byte_array()
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1))
+        .fold(U256::ZERO, |acc, bit| {
+            let acc = add_mod(&acc, &acc);
+
+            if bit == 1 {
+                add_mod(&acc, a)
+            } else {
+                acc
+            }
+        })
+}
+
+/// Check that `signature` is the signature of `msg` with `d` when `k` is
+/// derived as in RFC 6979 with the digest `D`.
+fn check_signature<D>(d: &NonZeroScalar<MockCurve>, msg: &[u8], signature: &Signature<MockCurve>)
+where
+    D: BlockInput + Digest + FixedOutput + Clone + Default + Reset + Update,
+{
+    let z = Scalar::from_digest(Digest::chain(D::new(), msg));
+    let k = Rfc6979::<D>::new().generate_nonce(d, &z).unwrap();
+    let (r, s) = (to_uint(&signature.r()), to_uint(&signature.s()));
+
+    assert_eq!(
+        mul_mod(&s, &to_uint(&k)),
+        add_mod(&to_uint(&z), &mul_mod(&r, &to_uint(d))),
+    );
+}
+
+#[test]
+fn matches_openssl_rfc6979_signatures() {
+    let mut count = 0;
+
+    for line in FIXTURES.lines() {
+        let fields = line.split(':').collect::<Vec<_>>();
+        assert_eq!(fields.len(), 4, "malformed fixture: {}", line);
+
+        let d = NonZeroScalar::<MockCurve>::try_from(decode_hex(fields[1]).as_slice()).unwrap();
+        let msg = decode_hex(fields[2]);
+        let der = decode_hex(fields[3]);
+
+        let signature = Signature::<MockCurve>::from_der(&der).unwrap();
+        assert_eq!(signature.to_der().as_bytes(), der.as_slice());
+
+        match fields[0] {
+            "sha224" => check_signature::<Sha224>(&d, &msg, &signature),
+            "sha256" => check_signature::<Sha256>(&d, &msg, &signature),
+            "sha384" => check_signature::<Sha384>(&d, &msg, &signature),
+            "sha512" => check_signature::<Sha512>(&d, &msg, &signature),
+            other => panic!("unsupported digest: {}", other),
+        }
+
+        count += 1;
+    }
+
+    assert_eq!(count, 32);
+}
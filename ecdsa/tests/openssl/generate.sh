@@ -0,0 +1,48 @@
+#!/bin/sh
+# Regenerate the OpenSSL deterministic ECDSA fixtures in `p256.txt`.
+#
+# Requires OpenSSL 3.2 or newer, which supports RFC 6979 nonces via
+# `-pkeyopt nonce-type:1`. Messages are signed with `pkeyutl -rawin`, so
+# OpenSSL hashes them itself.
+#
+# Each line of the output is `digest:d:message:signature`, with the secret
+# scalar, message, and ASN.1 DER signature hex encoded.
+
+set -eu
+
+cd "$(dirname "$0")"
+tmp="$(mktemp -d)"
+trap 'rm -rf "$tmp"' EXIT
+
+# Secret scalars: from RFC 6979 Appendix A.2.5, and random
+keys="c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721
+d53c5b41a766250b71e1ea08715ac88fe5502d51b864e36c9f009e3ceacf41d4"
+
+# Messages, hex encoded: "", "sample", "test", and 200 bytes of 0x61 ("a")
+msgs="73616d706c65 74657374 $(printf '61%.0s' $(seq 200))"
+
+hex() {
+    od -An -v -tx1 | tr -d ' \n'
+}
+
+unhex() {
+    sed 's/../\\x&/g' | xargs -0 printf '%b'
+}
+
+{
+    for d in $keys; do
+        # ECPrivateKey with the prime256v1 OID as its parameters
+        printf '30310201010420%sa00a06082a8648ce3d030107' "$d" | unhex > "$tmp/key.der"
+        openssl ec -inform DER -in "$tmp/key.der" -out "$tmp/key.pem" 2> /dev/null
+
+        for digest in sha224 sha256 sha384 sha512; do
+            for msg in "" $msgs; do
+                printf '%s' "$msg" | unhex > "$tmp/msg"
+                sig="$(openssl pkeyutl -sign -rawin -digest "$digest" \
+                    -inkey "$tmp/key.pem" -in "$tmp/msg" \
+                    -pkeyopt nonce-type:1 | hex)"
+                echo "$digest:$d:$msg:$sig"
+            done
+        done
+    done
+} > p256.txt
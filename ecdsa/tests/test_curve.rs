@@ -0,0 +1,492 @@
+//! Tests which use the arithmetic of `TestCurve`
+
+#![cfg(all(feature = "dev", feature = "verify"))]
+
+use core::convert::TryFrom;
+use ecdsa::{
+    dev::{
+        test_curve::{ProjectivePoint, Scalar, TestCurve, VerifyingKey},
+        InvalidPointVector,
+    },
+    hazmat::LinearCombination,
+    signature::Verifier,
+    RecoveryId,
+};
+use elliptic_curve::group::Group;
+use hex_literal::hex;
+
+#[cfg(feature = "sign")]
+use {
+    ecdsa::{
+        dev::test_curve::SigningKey,
+        nonce::{ExternalNonce, Rfc6979},
+    },
+    elliptic_curve::NonZeroScalar,
+    sha2::{Digest, Sha256},
+};
+
+#[cfg(feature = "sign")]
+use ecdsa::signature::{DigestSigner, Signer};
+
+#[cfg(feature = "getrandom")]
+use {ecdsa::hazmat::FromDigest, elliptic_curve::rand_core::OsRng};
+
+#[cfg(feature = "alloc")]
+type PrecomputedVerifyingKey = ecdsa::PrecomputedVerifyingKey<TestCurve>;
+type Signature = ecdsa::Signature<TestCurve>;
+
+/// Uncompressed SEC1 encoding of the public key for `0x0123456789abcdef`
+const EXAMPLE_KEY: [u8; 17] = hex!("049a3992d06c995ee5ef7b103efb096aeb");
+
+/// Message signed by [`EXAMPLE_SIGNATURE`]
+const EXAMPLE_MSG: &[u8] = b"example message";
+
+/// Signature of [`EXAMPLE_MSG`] with SHA-256 and `k = 0x0fedcba987654321`
+const EXAMPLE_SIGNATURE: [u8; 16] = hex!("f279f032eef2e54a94c1f3e453906713");
+
+/// Signature of [`EXAMPLE_MSG`] with `r = 2` and `s = 0x0123456789abcdef`,
+/// for which 𝐑 has the odd y-coordinate and x-coordinate `n + 2`
+const REDUCED_X_SIGNATURE: [u8; 16] = hex!("00000000000000020123456789abcdef");
+
+/// Public key for which [`REDUCED_X_SIGNATURE`] is valid
+const REDUCED_X_KEY: [u8; 17] = hex!("04fe19e58b9fe56504a46363b7b6adcb39");
+
+/// Invalid `TestCurve` public key encodings (x ≥ p, x not on the curve, and
+/// points which don't satisfy the curve equation).
+const INVALID_POINTS: &[InvalidPointVector] = &[
+    InvalidPointVector {
+        description: "compressed x = p",
+        sec1: &hex!("02ffffffffffffff43"),
+    },
+    InvalidPointVector {
+        description: "compressed x = p + 4, which reduces to the generator's x",
+        sec1: &hex!("02ffffffffffffff47"),
+    },
+    InvalidPointVector {
+        description: "compressed x = 1, which has no point on the curve",
+        sec1: &hex!("020000000000000001"),
+    },
+    InvalidPointVector {
+        description: "uncompressed (0, 0)",
+        sec1: &hex!("0400000000000000000000000000000000"),
+    },
+    InvalidPointVector {
+        description: "uncompressed generator with x + p",
+        sec1: &hex!("04ffffffffffffff47c8ff979844ad4580"),
+    },
+    InvalidPointVector {
+        description: "uncompressed generator with y + 1",
+        sec1: &hex!("040000000000000004c8ff979844ad4581"),
+    },
+];
+
+ecdsa::new_invalid_point_test!(TestCurve, INVALID_POINTS);
+
+#[test]
+fn lincomb_matches_separate_multiplications() {
+    let x = ProjectivePoint::generator() * Scalar::from(5);
+    let y = ProjectivePoint::generator() * Scalar::from(7);
+    let k = Scalar::from(0x1234_5678_90ab_cdef);
+    let l = Scalar::from(0xfedc_ba09_8765_4321);
+
+    assert_eq!(ProjectivePoint::lincomb(&x, &k, &y, &l), x * k + y * l);
+}
+
+#[test]
+fn verify_example_signature() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[test]
+fn reject_signature_of_other_message() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+    assert!(verifying_key.verify(b"other message", &signature).is_err());
+}
+
+#[test]
+fn verifying_key_from_str() {
+    // Parses SEC1 hex, with or without the `pem` feature
+    let verifying_key = "049a3992d06c995ee5ef7b103efb096aeb"
+        .parse::<VerifyingKey>()
+        .unwrap();
+
+    assert_eq!(
+        verifying_key,
+        VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap()
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn reject_precomputed_tables_for_other_key() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let other_key =
+        VerifyingKey::from_affine((ProjectivePoint::generator() * Scalar::from(2)).into()).unwrap();
+
+    let mut bytes = PrecomputedVerifyingKey::new(verifying_key).to_bytes();
+    let other_bytes = PrecomputedVerifyingKey::new(other_key).to_bytes();
+    bytes[19..].copy_from_slice(&other_bytes[19..]);
+
+    assert!(PrecomputedVerifyingKey::from_bytes(&bytes).is_err());
+    assert!(PrecomputedVerifyingKey::from_bytes(&other_bytes).is_ok());
+}
+
+#[cfg(all(feature = "alloc", feature = "sign"))]
+#[test]
+fn sign_many_matches_individual_signatures() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let msgs: [&[u8]; 3] = [b"first message", b"second message", b"third message"];
+
+    let signatures = signing_key
+        .try_sign_many(msgs.iter().map(|msg| Sha256::new().chain(msg)))
+        .unwrap();
+
+    assert_eq!(signatures.len(), msgs.len());
+
+    for (msg, signature) in msgs.iter().zip(&signatures) {
+        assert_eq!(signature, &signing_key.sign(msg));
+    }
+}
+
+#[test]
+fn verify_strict_rejects_high_s() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let mut signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+
+    // The example signature's `s` is greater than half the order
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+    assert!(verifying_key
+        .verify_strict(EXAMPLE_MSG, &signature)
+        .is_err());
+
+    assert!(signature.normalize_s().unwrap());
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+    assert!(verifying_key.verify_strict(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn recover_verifying_key() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    let (signature, recovery_id) = signing_key.try_sign_recoverable(EXAMPLE_MSG).unwrap();
+
+    let recovered = VerifyingKey::recover_from_msg(EXAMPLE_MSG, &signature, recovery_id);
+    assert_eq!(recovered.unwrap(), verifying_key);
+
+    let digest = Sha256::new().chain(EXAMPLE_MSG);
+    let recovered = VerifyingKey::recover_from_digest(digest, &signature, recovery_id);
+    assert_eq!(recovered.unwrap(), verifying_key);
+}
+
+#[test]
+fn recover_verifying_key_with_reduced_x() {
+    let signature = Signature::try_from(&REDUCED_X_SIGNATURE[..]).unwrap();
+    let expected_key = VerifyingKey::from_sec1_bytes(&REDUCED_X_KEY).unwrap();
+    assert!(expected_key.verify(EXAMPLE_MSG, &signature).is_ok());
+
+    let recovery_id = RecoveryId::new(true, true);
+    let recovered = VerifyingKey::recover_from_msg(EXAMPLE_MSG, &signature, recovery_id);
+    assert_eq!(recovered.unwrap(), expected_key);
+
+    // Without the reduction, 𝐑 is a different point (or none at all)
+    for &recovery_id in &[RecoveryId::new(false, false), RecoveryId::new(true, false)] {
+        let recovered = VerifyingKey::recover_from_msg(EXAMPLE_MSG, &signature, recovery_id);
+        assert!(recovered.map_or(true, |key| key != expected_key));
+    }
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn recover_rejects_wrong_recovery_id_and_message() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    let (signature, recovery_id) = signing_key.try_sign_recoverable(EXAMPLE_MSG).unwrap();
+
+    // Flipping the parity of 𝐑 recovers the key for its negation
+    let wrong_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    let recovered = VerifyingKey::recover_from_msg(EXAMPLE_MSG, &signature, wrong_id);
+    assert!(recovered.map_or(true, |key| key != verifying_key));
+
+    let recovered = VerifyingKey::recover_from_msg(b"other message", &signature, recovery_id);
+    assert!(recovered.map_or(true, |key| key != verifying_key));
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn only_returned_recovery_id_recovers_signer() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    for msg in [EXAMPLE_MSG, b"first message", b"second message"].iter() {
+        let (signature, recovery_id) = signing_key.try_sign_recoverable(msg).unwrap();
+        assert!(verifying_key.verify(msg, &signature).is_ok());
+
+        for byte in 0..=RecoveryId::MAX {
+            let candidate = RecoveryId::from_byte(byte).unwrap();
+            let recovered = VerifyingKey::recover_from_msg(msg, &signature, candidate);
+            let recovers_signer = matches!(recovered, Ok(key) if key == verifying_key);
+            assert_eq!(recovers_signer, candidate == recovery_id);
+        }
+    }
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn tweak_add_matches_verifying_key_tweak_add() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    let tweak = Scalar::from(0x1122_3344_5566_7788);
+
+    let tweaked_signing_key = signing_key.tweak_add(&tweak).unwrap();
+    let tweaked_verifying_key = verifying_key.tweak_add(&tweak).unwrap();
+    assert_eq!(tweaked_signing_key.verifying_key(), tweaked_verifying_key);
+
+    let signature = tweaked_signing_key.sign(EXAMPLE_MSG);
+    assert!(tweaked_verifying_key
+        .verify(EXAMPLE_MSG, &signature)
+        .is_ok());
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_err());
+
+    // Tweaking by -𝑑 gives the zero scalar and the identity point
+    let negated = -**signing_key.as_nonzero_scalar();
+    assert!(signing_key.tweak_add(&negated).is_err());
+    assert!(verifying_key.tweak_add(&negated).is_err());
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn sign_with_precomputed_nonce() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    let nonce = signing_key.precompute_nonce(&mut OsRng).unwrap();
+    let digest = Sha256::new().chain(EXAMPLE_MSG);
+    let signature = signing_key
+        .try_sign_with_precomputed_nonce(nonce, digest)
+        .unwrap();
+
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[cfg(all(feature = "alloc", feature = "getrandom"))]
+#[test]
+fn precomputed_nonces_sign_with_distinct_r() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    // Each nonce is consumed by the signature made with it
+    let signatures = signing_key
+        .precompute_nonces(&mut OsRng, 4)
+        .unwrap()
+        .into_iter()
+        .map(|nonce| {
+            let digest = Sha256::new().chain(EXAMPLE_MSG);
+            signing_key.try_sign_with_precomputed_nonce(nonce, digest)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    for (i, signature) in signatures.iter().enumerate() {
+        assert!(verifying_key.verify(EXAMPLE_MSG, signature).is_ok());
+
+        for other in &signatures[..i] {
+            assert_ne!(*signature.r(), *other.r());
+        }
+    }
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn session_signature_verifies() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    let session = signing_key.start_session(&mut OsRng).unwrap();
+    let digest = Sha256::new().chain(EXAMPLE_MSG);
+    let signature = session.try_sign_digest(digest).unwrap();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+
+    let session = signing_key.start_session(&mut OsRng).unwrap();
+    let hashed_msg = Scalar::from_digest(Sha256::new().chain(EXAMPLE_MSG));
+    let other_signature = session.try_sign_prehashed(&hashed_msg).unwrap();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &other_signature).is_ok());
+
+    // Each session has its own ephemeral scalar
+    assert_ne!(*signature.r(), *other_signature.r());
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn aborted_and_dropped_sessions_do_not_affect_later_sessions() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    // Both consume the session, so its ephemeral scalar can't be used again
+    signing_key.start_session(&mut OsRng).unwrap().abort();
+    drop(signing_key.start_session(&mut OsRng).unwrap());
+
+    let session = signing_key.start_session(&mut OsRng).unwrap();
+    let digest = Sha256::new().chain(EXAMPLE_MSG);
+    let signature = session.try_sign_digest(digest).unwrap();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn prepared_signing_key_matches_signing_key() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let prepared_key = signing_key.prepare::<Sha256>();
+
+    for msg in [EXAMPLE_MSG, b"first message", b"second message"].iter() {
+        let digest = Sha256::new().chain(msg);
+        let signature = prepared_key.try_sign_digest(digest.clone()).unwrap();
+        assert_eq!(signature, signing_key.try_sign_digest(digest).unwrap());
+    }
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn sign_digest_with_rfc6979_nonce_matches_sign_digest() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let mut nonce_generator = Rfc6979::<Sha256>::new();
+
+    for msg in [EXAMPLE_MSG, b"first message", b"second message"].iter() {
+        let digest = Sha256::new().chain(msg);
+        let signature = signing_key
+            .try_sign_digest_with_nonce(&mut nonce_generator, digest.clone())
+            .unwrap();
+        assert_eq!(signature, signing_key.try_sign_digest(digest).unwrap());
+    }
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn external_nonce_signs_only_once() {
+    let signing_key = SigningKey::from_bytes(&hex!("0123456789abcdef")).unwrap();
+    let k = NonZeroScalar::new(Scalar::from(0x0fed_cba9_8765_4321)).unwrap();
+    let mut nonce_generator = ExternalNonce::new(k);
+
+    let digest = Sha256::new().chain(EXAMPLE_MSG);
+    let signature = signing_key
+        .try_sign_digest_with_nonce(&mut nonce_generator, digest.clone())
+        .unwrap();
+    assert_eq!(signature.as_ref(), &EXAMPLE_SIGNATURE[..]);
+
+    assert!(signing_key
+        .try_sign_digest_with_nonce(&mut nonce_generator, digest)
+        .is_err());
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn derive_from_seed_is_deterministic() {
+    let signing_key = SigningKey::derive_from_seed(b"seed", b"info").unwrap();
+    assert_eq!(signing_key.to_bytes().as_slice(), hex!("52f3dd54074f35f0"));
+
+    let same_key = SigningKey::derive_from_seed(b"seed", b"info").unwrap();
+    assert_eq!(same_key.to_bytes(), signing_key.to_bytes());
+
+    let signature = signing_key.sign(EXAMPLE_MSG);
+    assert!(same_key
+        .verifying_key()
+        .verify(EXAMPLE_MSG, &signature)
+        .is_ok());
+}
+
+#[cfg(feature = "sign")]
+#[test]
+fn derive_from_seed_separates_info_and_seeds() {
+    let signing_key = SigningKey::derive_from_seed(b"seed", b"info").unwrap();
+
+    let other_info = SigningKey::derive_from_seed(b"seed", b"other info").unwrap();
+    assert_eq!(other_info.to_bytes().as_slice(), hex!("8f99ecc8293dfec7"));
+
+    let other_seed = SigningKey::derive_from_seed(b"other seed", b"info").unwrap();
+    assert_ne!(other_seed.to_bytes(), signing_key.to_bytes());
+    assert_ne!(other_seed.to_bytes(), other_info.to_bytes());
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn try_random_keys_are_distinct() {
+    let signing_key = SigningKey::try_random(&mut OsRng).unwrap();
+    let other_key = SigningKey::try_random(&mut OsRng).unwrap();
+    assert_ne!(signing_key.to_bytes(), other_key.to_bytes());
+
+    let signature = signing_key.sign(EXAMPLE_MSG);
+    let verifying_key = signing_key.verifying_key();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[cfg(feature = "getrandom")]
+#[test]
+fn random_checked_key_signs_and_verifies() {
+    let signing_key = SigningKey::random_checked(&mut OsRng).unwrap();
+    assert!(signing_key.pairwise_consistency_test().is_ok());
+
+    let signature = signing_key.sign(EXAMPLE_MSG);
+    let verifying_key = signing_key.verifying_key();
+    assert!(verifying_key.verify(EXAMPLE_MSG, &signature).is_ok());
+}
+
+#[test]
+fn verify_context_limits_message_size() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+    let (head, tail) = EXAMPLE_MSG.split_at(7);
+
+    let mut context = verifying_key
+        .verify_context()
+        .with_max_len(EXAMPLE_MSG.len() as u64);
+    context.update(head).unwrap();
+    context.update(tail).unwrap();
+    assert!(context.verify(&signature).is_ok());
+
+    // Once a chunk is rejected, verification fails even without it
+    let mut context = verifying_key
+        .verify_context()
+        .with_max_len(EXAMPLE_MSG.len() as u64 - 1);
+    context.update(head).unwrap();
+    assert!(context.update(tail).is_err());
+    assert!(context.verify(&signature).is_err());
+}
+
+#[test]
+fn verify_context_reports_progress() {
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+    let reported = core::cell::RefCell::new(Vec::new());
+    let progress = |len| reported.borrow_mut().push(len);
+
+    let mut context = verifying_key.verify_context().with_progress(&progress);
+
+    for chunk in EXAMPLE_MSG.chunks(4) {
+        context.update(chunk).unwrap();
+    }
+
+    assert_eq!(context.len(), EXAMPLE_MSG.len() as u64);
+    assert!(context.verify(&signature).is_ok());
+    assert_eq!(*reported.borrow(), [4, 8, 12, 15]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn verify_context_io_write_limits_message_size() {
+    use std::io::{self, Write};
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(&EXAMPLE_KEY).unwrap();
+    let signature = Signature::try_from(&EXAMPLE_SIGNATURE[..]).unwrap();
+
+    let mut context = verifying_key.verify_context().with_max_len(15);
+    io::copy(&mut &EXAMPLE_MSG[..], &mut context).unwrap();
+    assert!(context.verify(&signature).is_ok());
+
+    let mut context = verifying_key.verify_context().with_max_len(14);
+    let err = context.write_all(EXAMPLE_MSG).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(context.verify(&signature).is_err());
+}
@@ -0,0 +1,61 @@
+//! Checks that secret values don't survive in heap memory after being dropped
+
+#![cfg(all(feature = "dev", feature = "sign", feature = "std"))]
+
+use ecdsa::{elliptic_curve::NonZeroScalar, nonce::ExternalNonce};
+use elliptic_curve::dev::MockCurve;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    slice,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Secret value whose bytes are the same in any limb order
+const CANARY: [u8; 32] = [0x5a; 32];
+
+/// Global allocator which checks whether freed blocks contain [`CANARY`]
+struct InspectingAllocator;
+
+static CANARY_FREED: AtomicBool = AtomicBool::new(false);
+
+unsafe impl GlobalAlloc for InspectingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let block = slice::from_raw_parts(ptr, layout.size());
+
+        if block.windows(CANARY.len()).any(|window| window == CANARY) {
+            CANARY_FREED.store(true, Ordering::SeqCst);
+        }
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: InspectingAllocator = InspectingAllocator;
+
+/// Does dropping the value returned by `f` free memory containing the canary?
+fn frees_canary<T>(f: impl FnOnce() -> Box<T>) -> bool {
+    let value = f();
+    CANARY_FREED.store(false, Ordering::SeqCst);
+    drop(value);
+    CANARY_FREED.load(Ordering::SeqCst)
+}
+
+fn canary_scalar() -> NonZeroScalar<MockCurve> {
+    NonZeroScalar::from_repr(CANARY.into()).expect("invalid scalar")
+}
+
+// Each check is run from a single test so concurrent tests can't interfere
+#[test]
+fn secrets_are_zeroized_on_drop() {
+    // The harness detects secrets which are not zeroized
+    assert!(frees_canary(|| Box::new(CANARY)));
+
+    assert!(!frees_canary(|| Box::new(ExternalNonce::new(
+        canary_scalar()
+    ))));
+}
@@ -262,6 +262,21 @@ extern crate std;
 
 pub use signature::{self, Error};
 
+#[cfg(feature = "pgp")]
+mod pgp;
+
+#[cfg(feature = "pgp")]
+pub use crate::pgp::PGP_MPIS_MAX_LENGTH;
+
+/// Commonly used traits, re-exported for convenience.
+///
+/// ```
+/// use ed25519::prelude::*;
+/// ```
+pub mod prelude {
+    pub use crate::signature::{Signature as _, Signer, Verifier};
+}
+
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{self, Debug},
@@ -0,0 +1,170 @@
+//! Support for Ed25519 signatures encoded as OpenPGP multiprecision integers
+//! (MPIs).
+//!
+//! OpenPGP signature packets encode the algorithm-specific fields of an EdDSA
+//! signature as two MPIs containing the native encodings of `R` and `S`
+//! ([RFC 4880bis Section 5.2.3]). Each MPI is a two-octet big endian bit count
+//! followed by the value with leading zero octets removed.
+//!
+//! [RFC 4880bis Section 5.2.3]: https://datatracker.ietf.org/doc/html/draft-ietf-openpgp-rfc4880bis-10#section-5.2.3
+
+use crate::{Error, Signature, SIGNATURE_LENGTH};
+use core::convert::TryFrom;
+
+/// Length of `R` and `S`
+const COMPONENT_LENGTH: usize = SIGNATURE_LENGTH / 2;
+
+/// Maximum length of a signature encoded as OpenPGP MPIs
+pub const PGP_MPIS_MAX_LENGTH: usize = SIGNATURE_LENGTH + 4;
+
+impl Signature {
+    /// Parse a signature from the `R` and `S` MPIs of an OpenPGP signature
+    /// packet.
+    ///
+    /// The input must contain exactly two MPIs, each of which must be
+    /// minimally encoded.
+    pub fn from_pgp_mpis(bytes: &[u8]) -> Result<Self, Error> {
+        let (r, bytes) = decode_mpi(bytes)?;
+        let (s, bytes) = decode_mpi(bytes)?;
+
+        if !bytes.is_empty() || r.len() > COMPONENT_LENGTH || s.len() > COMPONENT_LENGTH {
+            return Err(Error::new());
+        }
+
+        let mut arr = [0u8; SIGNATURE_LENGTH];
+        arr[(COMPONENT_LENGTH - r.len())..COMPONENT_LENGTH].copy_from_slice(r);
+        arr[(SIGNATURE_LENGTH - s.len())..].copy_from_slice(s);
+        Signature::try_from(&arr[..])
+    }
+
+    /// Serialize this signature as the `R` and `S` MPIs of an OpenPGP
+    /// signature packet, writing them into the provided buffer.
+    ///
+    /// Returns the written portion of the buffer, which must be at least
+    /// [`PGP_MPIS_MAX_LENGTH`] bytes.
+    pub fn encode_pgp_mpis<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let (r, s) = self.0.split_at(COMPONENT_LENGTH);
+        let r_len = encode_mpi(r, buf)?;
+        let s_len = encode_mpi(s, &mut buf[r_len..])?;
+        Ok(&buf[..(r_len + s_len)])
+    }
+}
+
+/// Decode an MPI from the front of the given bytes, returning its value and
+/// the remaining input.
+fn decode_mpi(bytes: &[u8]) -> Result<(&[u8], &[u8]), Error> {
+    if bytes.len() < 2 {
+        return Err(Error::new());
+    }
+
+    let bits = usize::from(u16::from_be_bytes([bytes[0], bytes[1]]));
+    let len = bits / 8 + usize::from(bits % 8 != 0);
+    let bytes = &bytes[2..];
+
+    if bytes.len() < len {
+        return Err(Error::new());
+    }
+
+    let (value, rest) = bytes.split_at(len);
+
+    if value.first().map_or(0, |&byte| mpi_bits(byte, len)) != bits {
+        return Err(Error::new());
+    }
+
+    Ok((value, rest))
+}
+
+/// Encode the given big endian value as an MPI, returning its encoded length.
+fn encode_mpi(value: &[u8], buf: &mut [u8]) -> Result<usize, Error> {
+    let value = &value[value.iter().take_while(|&&byte| byte == 0).count()..];
+    let bits = value.first().map_or(0, |&byte| mpi_bits(byte, value.len()));
+    let bits = u16::try_from(bits).map_err(|_| Error::new())?;
+    let encoded_len = 2 + value.len();
+
+    if buf.len() < encoded_len {
+        return Err(Error::new());
+    }
+
+    buf[..2].copy_from_slice(&bits.to_be_bytes());
+    buf[2..encoded_len].copy_from_slice(value);
+    Ok(encoded_len)
+}
+
+/// Bit length of a `len`-byte big endian value beginning with `first_byte`.
+fn mpi_bits(first_byte: u8, len: usize) -> usize {
+    (len * 8).saturating_sub(first_byte.leading_zeros() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_mpi, encode_mpi, PGP_MPIS_MAX_LENGTH};
+    use crate::{Signature, SIGNATURE_LENGTH};
+
+    #[test]
+    fn pgp_mpi_roundtrip() {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes[1] = 0x40;
+        bytes[32] = 0xff;
+        bytes[63] = 0x0f;
+        let signature = Signature::new(bytes);
+
+        let mut buf = [0u8; PGP_MPIS_MAX_LENGTH];
+        let mpis = signature.encode_pgp_mpis(&mut buf).unwrap();
+        assert_eq!(mpis.len(), PGP_MPIS_MAX_LENGTH - 1);
+
+        // `R` has a leading zero byte and 0x40 has one leading zero bit
+        assert_eq!(&mpis[..2], &[0x00, 0xf7]);
+        assert_eq!(&mpis[33..35], &[0x01, 0x00]);
+
+        assert_eq!(Signature::from_pgp_mpis(mpis).unwrap(), signature);
+        assert!(Signature::from_pgp_mpis(&buf).is_err());
+    }
+
+    #[test]
+    fn mpi_helpers() {
+        let mut buf = [0xffu8; 4];
+
+        // Zero is encoded as a bit count of zero with no value octets
+        assert_eq!(encode_mpi(&[0x00, 0x00], &mut buf).unwrap(), 2);
+        assert_eq!(&buf[..2], &[0x00, 0x00]);
+        assert_eq!(
+            decode_mpi(&[0x00, 0x00, 0xaa]).unwrap(),
+            (&[][..], &[0xaa][..])
+        );
+
+        // Leading zero octets are stripped and leading zero bits are not counted
+        assert_eq!(encode_mpi(&[0x00, 0x01, 0xff], &mut buf).unwrap(), 4);
+        assert_eq!(buf, [0x00, 0x09, 0x01, 0xff]);
+        assert_eq!(decode_mpi(&buf).unwrap(), (&[0x01, 0xff][..], &[][..]));
+
+        // Output buffer too small
+        assert!(encode_mpi(&[0x01, 0xff], &mut buf[..3]).is_err());
+
+        // Non-minimal encodings
+        assert!(decode_mpi(&[0x00, 0x08, 0x00]).is_err());
+        assert!(decode_mpi(&[0x00, 0x10, 0x00, 0xff]).is_err());
+
+        // Truncated
+        assert!(decode_mpi(&[0x00]).is_err());
+        assert!(decode_mpi(&[0xff, 0xff, 0x01]).is_err());
+    }
+
+    #[test]
+    fn zero_length_and_oversized_pgp_mpis() {
+        let mut bytes = [0u8; SIGNATURE_LENGTH];
+        bytes[63] = 0x01;
+        let signature = Signature::new(bytes);
+
+        // An all-zero `R` is encoded as a zero-length MPI
+        let mut buf = [0u8; PGP_MPIS_MAX_LENGTH + 1];
+        let mpis = signature.encode_pgp_mpis(&mut buf).unwrap();
+        assert_eq!(mpis, &[0x00, 0x00, 0x00, 0x01, 0x01]);
+        assert_eq!(Signature::from_pgp_mpis(mpis).unwrap(), signature);
+
+        // 33-byte `R`, which is longer than a component
+        buf[..3].copy_from_slice(&[0x01, 0x01, 0x01]);
+        buf[3..35].copy_from_slice(&[0u8; 32]);
+        buf[35..38].copy_from_slice(&[0x00, 0x01, 0x01]);
+        assert!(Signature::from_pgp_mpis(&buf[..38]).is_err());
+    }
+}